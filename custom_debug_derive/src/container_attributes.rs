@@ -0,0 +1,67 @@
+use darling::util::{Flag, Override};
+use darling::FromMeta;
+
+use crate::field_attributes::SortedOptions;
+
+#[derive(FromMeta, Default)]
+pub struct ContainerAttributes {
+    pub compact_code: Flag,
+    /// Sorts every `HashMap` and `HashSet` field.
+    pub sorted: Option<Override<SortedOptions>>,
+    /// Elides derived values nested deeper than this below the type.
+    /// Without a value, the type only counts towards the depth.
+    pub max_depth: Option<Override<usize>>,
+    /// Caps the output of the type to this many bytes.
+    pub max_bytes: Option<usize>,
+    /// Lets field filters hide named fields of the type.
+    pub filter: Flag,
+    /// Also derives `defmt::Format`.
+    pub defmt: Flag,
+    /// Also derives `valuable::Valuable`.
+    pub valuable: Flag,
+    /// Also derives `custom_debug::SerializeDebug`.
+    pub serialize: Flag,
+    /// Also derives `ufmt::uDebug`.
+    pub ufmt: Flag,
+    /// Also derives `custom_debug::Visit`.
+    pub visit: Flag,
+}
+
+impl ContainerAttributes {
+    pub fn try_combine(self, other: Self) -> darling::Result<Self> {
+        let sorted = match (self.sorted, other.sorted) {
+            (Some(_), Some(_)) => return Err(darling::Error::duplicate_field("sorted")),
+            (a, b) => a.or(b),
+        };
+
+        let max_depth = match (self.max_depth, other.max_depth) {
+            (Some(_), Some(_)) => return Err(darling::Error::duplicate_field("max_depth")),
+            (a, b) => a.or(b),
+        };
+        let max_bytes = match (self.max_bytes, other.max_bytes) {
+            (Some(_), Some(_)) => return Err(darling::Error::duplicate_field("max_bytes")),
+            (a, b) => a.or(b),
+        };
+
+        Ok(Self {
+            compact_code: combine_flags(self.compact_code, other.compact_code),
+            sorted,
+            max_depth,
+            max_bytes,
+            filter: combine_flags(self.filter, other.filter),
+            defmt: combine_flags(self.defmt, other.defmt),
+            valuable: combine_flags(self.valuable, other.valuable),
+            serialize: combine_flags(self.serialize, other.serialize),
+            ufmt: combine_flags(self.ufmt, other.ufmt),
+            visit: combine_flags(self.visit, other.visit),
+        })
+    }
+}
+
+fn combine_flags(a: Flag, b: Flag) -> Flag {
+    if a.is_present() {
+        a
+    } else {
+        b
+    }
+}
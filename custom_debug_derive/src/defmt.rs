@@ -0,0 +1,195 @@
+//! `defmt::Format` impls derived from the same attributes as `Debug`.
+//!
+//! Fields are encoded with their own `Format` impl, unless they use a formatter,
+//! in which case the formatted output is sent through `defmt::Debug2Format`.
+//! Fields hidden by field filters or their `level` are left out.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{DeriveInput, Fields, Result};
+use synstructure::{AddBounds, Structure, VariantInfo};
+
+use crate::container_attributes::ContainerAttributes;
+use crate::field_attributes::SkipMode;
+use crate::result_into_stream_ext::ResultIntoStreamExt;
+use crate::{
+    add_field_bounds, filter_out_skipped_fields, generate_debug_impl, generate_view_conditions,
+    parse_container_attributes, parse_field_attributes, type_name,
+};
+
+/// A field as it is written by the `Format` impl.
+struct Field {
+    /// Shows the field only if true
+    condition: Option<TokenStream>,
+    /// Format string for the field, without separator
+    format: String,
+    value: TokenStream,
+}
+
+/// Generates an impl of `defmt::Format`.
+pub fn generate_impl(input: &DeriveInput) -> Result<TokenStream> {
+    let mut structure = Structure::try_new(input)?;
+    let container_attributes = parse_container_attributes(&structure)?;
+
+    filter_out_skipped_fields(&mut structure, &container_attributes)?;
+    structure.add_bounds(AddBounds::None);
+
+    if cfg!(feature = "minimal") {
+        let match_arms = structure.each_variant(|variant| {
+            let name = match variant.prefix {
+                Some(prefix) => format!("{}::{}", prefix, variant.ast().ident),
+                None => variant.ast().ident.to_string(),
+            };
+
+            quote! { ::custom_debug::__private::defmt::write!(fmt, #name) }
+        });
+
+        return Ok(structure.gen_impl(quote! {
+            // `defmt::write!` expands to paths starting with `defmt::`
+            use ::custom_debug::__private::defmt;
+
+            gen impl ::custom_debug::__private::defmt::Format for @Self {
+                fn format(&self, fmt: ::custom_debug::__private::defmt::Formatter) {
+                    match self {
+                        #match_arms
+                    }
+                }
+            }
+        }));
+    }
+
+    add_field_bounds(
+        &mut structure,
+        &container_attributes,
+        quote! { ::custom_debug::__private::defmt::Format },
+    )?;
+
+    let match_arms = structure.each_variant(|variant| {
+        generate_match_arm_body(variant, &container_attributes).into_stream()
+    });
+
+    Ok(structure.gen_impl(quote! {
+        // `defmt::write!` expands to paths starting with `defmt::`
+        use ::custom_debug::__private::defmt;
+
+        gen impl ::custom_debug::__private::defmt::Format for @Self {
+            fn format(&self, fmt: ::custom_debug::__private::defmt::Formatter) {
+                match self {
+                    #match_arms
+                }
+            }
+        }
+    }))
+}
+
+fn generate_match_arm_body(
+    variant: &VariantInfo,
+    container_attributes: &ContainerAttributes,
+) -> Result<TokenStream> {
+    let name = variant.ast().ident.to_string();
+    let type_name = type_name(variant);
+    let mut fields = Vec::new();
+
+    for binding in variant.bindings() {
+        let field_attributes = parse_field_attributes(binding, container_attributes)?;
+
+        let mut conditions = match &field_attributes.skip_mode {
+            SkipMode::Default => Vec::new(),
+            SkipMode::Condition(condition) => vec![quote! { !#condition(#binding) }],
+            SkipMode::Sensitive => vec![quote! { ::custom_debug::__private::REVEAL }],
+            SkipMode::Always => continue,
+        };
+
+        conditions.extend(generate_view_conditions(
+            binding,
+            &field_attributes,
+            container_attributes,
+            &type_name,
+        ));
+
+        let condition = match conditions.is_empty() {
+            true => None,
+            false => Some(quote! { #(#conditions)&&* }),
+        };
+
+        let value = match field_attributes.debug_format.is_plain() {
+            true => quote! { #binding },
+            false => {
+                let value = generate_debug_impl(binding, &field_attributes.debug_format);
+
+                quote! { ::custom_debug::__private::defmt::Debug2Format(#value) }
+            }
+        };
+
+        let format = match &binding.ast().ident {
+            Some(ident) => format!("{}: {{}}", ident),
+            None => "{}".to_string(),
+        };
+
+        fields.push(Field {
+            condition,
+            format,
+            value,
+        });
+    }
+
+    let (open, close) = match variant.ast().fields {
+        Fields::Named(_) | Fields::Unit => (" {{ ", " }}"),
+        Fields::Unnamed(_) => ("(", ")"),
+    };
+
+    if fields.is_empty() {
+        return Ok(quote! { ::custom_debug::__private::defmt::write!(fmt, #name) });
+    }
+
+    // Without conditions, the whole variant is written at once
+    if fields.iter().all(|field| field.condition.is_none()) {
+        let formats = fields
+            .iter()
+            .map(|field| field.format.as_str())
+            .collect::<Vec<_>>();
+        let format = format!("{}{}{}{}", name, open, formats.join(", "), close);
+        let values = fields.iter().map(|field| &field.value);
+
+        return Ok(quote! { ::custom_debug::__private::defmt::write!(fmt, #format, #(#values),*) });
+    }
+
+    let writes = fields.iter().map(|field| {
+        let Field {
+            condition,
+            format,
+            value,
+        } = field;
+        let first_format = format!("{}{}", open, format);
+        let format = format!(", {}", format);
+        let write = quote! {
+            if first {
+                ::custom_debug::__private::defmt::write!(fmt, #first_format, #value);
+            } else {
+                ::custom_debug::__private::defmt::write!(fmt, #format, #value);
+            }
+            first = false;
+        };
+
+        match condition {
+            Some(condition) => quote! {
+                if #condition {
+                    #write
+                }
+            },
+            None => write,
+        }
+    });
+
+    Ok(quote! {
+        let mut first = true;
+
+        ::custom_debug::__private::defmt::write!(fmt, #name);
+
+        #(#writes)*
+
+        if !first {
+            ::custom_debug::__private::defmt::write!(fmt, #close);
+        }
+    })
+}
@@ -1,47 +1,149 @@
-use darling::util::Flag;
+use darling::ast::NestedMeta;
+use darling::util::{Flag, Override};
 use darling::FromMeta;
-use syn::ExprPath;
+use proc_macro2::Literal;
+use quote::quote;
+use syn::{parse_quote, Expr, ExprPath, Meta, MetaNameValue, Type};
+
+use crate::container_attributes::ContainerAttributes;
 
 #[derive(Default)]
 pub struct FieldAttributes {
     pub skip_mode: SkipMode,
+    /// Verbosity from which the field is shown.
+    pub level: Option<usize>,
     pub debug_format: DebugFormat,
+    /// Formats the (projected) value in derived `ufmt::uDebug` impls.
+    pub ufmt_with: Option<ExprPath>,
 }
 
 impl FieldAttributes {
     fn new(internal: InternalFieldAttributes) -> darling::Result<Self> {
         let mut skip_mode = SkipMode::Default;
-        let mut debug_format = DebugFormat::Default;
+        let mut debug_format = DebugFormat::default();
 
-        if internal.skip.is_present() {
-            skip_mode = skip_mode.try_combine(SkipMode::Always)?;
+        if let Some(skip) = internal.skip {
+            let skip = match skip.unwrap_or_default().sensitive.is_present() {
+                false => SkipMode::Always,
+                true => SkipMode::Sensitive,
+            };
+            skip_mode = skip_mode.try_combine(skip)?;
         }
 
         if let Some(skip_if) = internal.skip_if {
             skip_mode = skip_mode.try_combine(SkipMode::Condition(skip_if))?;
         }
 
+        if internal.redact.is_present() {
+            debug_format = debug_format.try_combine(DebugFormat::redact())?;
+        }
+
+        if let Some(via) = internal.via {
+            debug_format = debug_format.try_combine(DebugFormat::via(via))?;
+        }
+
         if let Some(format) = internal.format {
-            debug_format = debug_format.try_combine(DebugFormat::Format(format))?;
+            debug_format = debug_format.try_combine(DebugFormat::format(format))?;
+        }
+
+        if let Some(FormatterOption(with)) = internal.with {
+            debug_format = debug_format.try_combine(DebugFormat::with(with))?;
+        }
+
+        for (shorthand, with) in [
+            (internal.ipv4, parse_quote!(::custom_debug::ipv4)),
+            (internal.ipv6, parse_quote!(::custom_debug::ipv6)),
+            (internal.mac, parse_quote!(::custom_debug::mac)),
+            (
+                internal.ipv4_socket,
+                parse_quote!(::custom_debug::ipv4_socket),
+            ),
+            (
+                internal.ipv6_socket,
+                parse_quote!(::custom_debug::ipv6_socket),
+            ),
+            (internal.uuid, parse_quote!(::custom_debug::uuid)),
+            (internal.guid, parse_quote!(::custom_debug::guid)),
+            (internal.shared, parse_quote!(::custom_debug::shared)),
+        ] {
+            if shorthand.is_present() {
+                debug_format = debug_format.try_combine(DebugFormat::with(with))?;
+            }
+        }
+
+        if let Some(be) = internal.be {
+            let with = match be.unwrap_or_default().raw.is_present() {
+                false => parse_quote!(::custom_debug::be),
+                true => parse_quote!(::custom_debug::be_raw),
+            };
+            debug_format = debug_format.try_combine(DebugFormat::with(with))?;
         }
 
-        if let Some(with) = internal.with {
-            debug_format = debug_format.try_combine(DebugFormat::With(with))?;
+        if let Some(le) = internal.le {
+            let with = match le.unwrap_or_default().raw.is_present() {
+                false => parse_quote!(::custom_debug::le),
+                true => parse_quote!(::custom_debug::le_raw),
+            };
+            debug_format = debug_format.try_combine(DebugFormat::with(with))?;
+        }
+
+        if let Some(base64) = internal.base64 {
+            let with = base64.unwrap_or_default().formatter();
+            debug_format = debug_format.try_combine(DebugFormat::with(with))?;
+        }
+
+        if let Some(sorted) = internal.sorted {
+            let with = sorted.unwrap_or_default().formatter();
+            debug_format = debug_format.try_combine(DebugFormat::with(with))?;
+        }
+
+        if let Some(timestamp) = internal.timestamp {
+            let with = timestamp.unwrap_or_default().unit.formatter();
+            debug_format = debug_format.try_combine(DebugFormat::with(with))?;
         }
 
         Ok(Self {
             skip_mode,
+            level: internal.level,
             debug_format,
+            ufmt_with: internal.ufmt_with,
         })
     }
 
+    /// Applies container attributes that act as defaults for fields.
+    pub fn with_container_defaults(
+        mut self,
+        container_attributes: &ContainerAttributes,
+        ty: &Type,
+    ) -> Self {
+        let debug_format = &mut self.debug_format;
+
+        if let Some(sorted) = &container_attributes.sorted {
+            if debug_format.via.is_none() && debug_format.with.is_none() && is_hash_collection(ty) {
+                debug_format.with = Some(sorted.clone().unwrap_or_default().formatter());
+            }
+        }
+
+        self
+    }
+
     pub fn try_combine(self, other: Self) -> darling::Result<Self> {
         let skip_mode = self.skip_mode.try_combine(other.skip_mode)?;
         let debug_format = self.debug_format.try_combine(other.debug_format)?;
+        let level = match (self.level, other.level) {
+            (Some(_), Some(_)) => return Err(darling::Error::duplicate_field("level")),
+            (a, b) => a.or(b),
+        };
+        let ufmt_with = match (self.ufmt_with, other.ufmt_with) {
+            (Some(_), Some(_)) => return Err(darling::Error::duplicate_field("ufmt_with")),
+            (a, b) => a.or(b),
+        };
 
         Ok(Self {
             skip_mode,
+            level,
             debug_format,
+            ufmt_with,
         })
     }
 }
@@ -68,22 +170,68 @@ impl FromMeta for FieldAttributes {
     }
 }
 
-#[derive(FromMeta, Debug, PartialEq, Eq, Default)]
-pub enum DebugFormat {
-    #[default]
-    Default,
-    Format(String),
-    With(ExprPath),
+/// How a field is formatted, applied in the order `via`, `with`, `format`, `redact`.
+#[derive(Debug, PartialEq, Eq, Default)]
+pub struct DebugFormat {
+    /// Projects the field to another value first.
+    pub via: Option<ExprPath>,
+    /// Formats the (projected) value.
+    pub with: Option<Expr>,
+    /// Wraps the output in a format string.
+    pub format: Option<String>,
+    /// Hides the output, unless built with `--cfg custom_debug_reveal`.
+    pub redact: bool,
 }
 
 impl DebugFormat {
-    fn try_combine(self, other: Self) -> darling::Result<Self> {
-        match (&self, &other) {
-            (DebugFormat::Default, _) => Ok(other),
-            (_, DebugFormat::Default) => Ok(self),
-            _ => Err(conflicting_format_options_error()),
+    /// Whether the field is shown as is.
+    pub fn is_plain(&self) -> bool {
+        self.via.is_none() && self.with.is_none() && self.format.is_none() && !self.redact
+    }
+
+    fn via(via: ExprPath) -> Self {
+        Self {
+            via: Some(via),
+            ..Self::default()
         }
     }
+
+    fn with(with: Expr) -> Self {
+        Self {
+            with: Some(with),
+            ..Self::default()
+        }
+    }
+
+    fn format(format: String) -> Self {
+        Self {
+            format: Some(format),
+            ..Self::default()
+        }
+    }
+
+    fn redact() -> Self {
+        Self {
+            redact: true,
+            ..Self::default()
+        }
+    }
+
+    fn try_combine(self, other: Self) -> darling::Result<Self> {
+        Ok(Self {
+            via: try_combine_format_option(self.via, other.via)?,
+            with: try_combine_format_option(self.with, other.with)?,
+            format: try_combine_format_option(self.format, other.format)?,
+            redact: self.redact || other.redact,
+        })
+    }
+}
+
+fn try_combine_format_option<T>(a: Option<T>, b: Option<T>) -> darling::Result<Option<T>> {
+    match (a, b) {
+        (Some(_), Some(_)) => Err(conflicting_format_options_error()),
+        (a, b) => Ok(a.or(b)),
+    }
 }
 
 #[derive(Default, PartialEq, Eq)]
@@ -91,6 +239,8 @@ pub enum SkipMode {
     #[default]
     Default,
     Condition(ExprPath),
+    /// Skipped unless built with `--cfg custom_debug_reveal`.
+    Sensitive,
     Always,
 }
 
@@ -106,10 +256,160 @@ impl SkipMode {
 
 #[derive(FromMeta)]
 struct InternalFieldAttributes {
-    skip: Flag,
+    skip: Option<Override<SkipOptions>>,
     skip_if: Option<ExprPath>,
+    level: Option<usize>,
     format: Option<String>,
-    with: Option<ExprPath>,
+    with: Option<FormatterOption>,
+    via: Option<ExprPath>,
+    ufmt_with: Option<ExprPath>,
+    redact: Flag,
+    timestamp: Option<Override<TimestampOptions>>,
+    ipv4: Flag,
+    ipv6: Flag,
+    mac: Flag,
+    ipv4_socket: Flag,
+    ipv6_socket: Flag,
+    uuid: Flag,
+    guid: Flag,
+    be: Option<Override<IntOptions>>,
+    le: Option<Override<IntOptions>>,
+    base64: Option<Override<Base64Options>>,
+    sorted: Option<Override<SortedOptions>>,
+    shared: Flag,
+}
+
+#[derive(FromMeta, Default)]
+struct SkipOptions {
+    sensitive: Flag,
+}
+
+#[derive(FromMeta, Default)]
+struct IntOptions {
+    raw: Flag,
+}
+
+#[derive(FromMeta, Default)]
+struct Base64Options {
+    url: Flag,
+    max: Option<usize>,
+}
+
+impl Base64Options {
+    fn formatter(&self) -> Expr {
+        let url = self.url.is_present();
+
+        match self.max.map(Literal::usize_unsuffixed) {
+            None if url => parse_quote!(::custom_debug::base64url),
+            None => parse_quote!(::custom_debug::base64),
+            Some(max) if url => parse_quote!(::custom_debug::base64url_truncated::<#max>),
+            Some(max) => parse_quote!(::custom_debug::base64_truncated::<#max>),
+        }
+    }
+}
+
+/// Either a formatter expression (`with = hexbuf`)
+/// or a configurable formatter type and its options (`with(HexBuf, max = 32)`).
+struct FormatterOption(Expr);
+
+impl FromMeta for FormatterOption {
+    fn from_expr(expr: &Expr) -> darling::Result<Self> {
+        Expr::from_expr(expr).map(Self)
+    }
+
+    fn from_list(items: &[NestedMeta]) -> darling::Result<Self> {
+        let Some((formatter, options)) = items.split_first() else {
+            return Err(darling::Error::too_few_items(1));
+        };
+
+        let NestedMeta::Meta(Meta::Path(formatter)) = formatter else {
+            return Err(darling::Error::custom("Expected a formatter type").with_span(formatter));
+        };
+
+        let fields = options
+            .iter()
+            .map(|option| match option {
+                NestedMeta::Meta(Meta::NameValue(MetaNameValue { path, value, .. }))
+                    if path.get_ident().is_some() =>
+                {
+                    Ok(quote! { #path: #value })
+                }
+                _ => Err(darling::Error::custom("Expected `option = value`").with_span(option)),
+            })
+            .collect::<darling::Result<Vec<_>>>()?;
+
+        Ok(Self(parse_quote! {
+            {
+                #[allow(clippy::needless_update)]
+                let formatter = #formatter {
+                    #(#fields,)*
+                    ..::core::default::Default::default()
+                };
+
+                formatter
+            }
+        }))
+    }
+}
+
+#[derive(FromMeta, Default, Clone)]
+pub struct SortedOptions {
+    by_debug: Flag,
+}
+
+impl SortedOptions {
+    fn formatter(&self) -> Expr {
+        match self.by_debug.is_present() {
+            false => parse_quote!(::custom_debug::sorted),
+            // A macro, to fail with a clear error without `alloc`
+            true => parse_quote!(::custom_debug::__private::sorted_by_debug!()),
+        }
+    }
+}
+
+/// Whether `ty` is a `HashMap` or `HashSet`.
+///
+/// Only the last path segment is compared, so any type named like them matches,
+/// and aliases of them don't.
+fn is_hash_collection(ty: &Type) -> bool {
+    let Type::Path(ty) = ty else {
+        return false;
+    };
+
+    ty.path
+        .segments
+        .last()
+        .is_some_and(|segment| segment.ident == "HashMap" || segment.ident == "HashSet")
+}
+
+#[derive(FromMeta, Default)]
+struct TimestampOptions {
+    #[darling(default)]
+    unit: TimeUnit,
+}
+
+#[derive(FromMeta, Default)]
+enum TimeUnit {
+    #[default]
+    #[darling(rename = "s")]
+    Seconds,
+    #[darling(rename = "ms")]
+    Millis,
+    #[darling(rename = "us")]
+    Micros,
+    #[darling(rename = "ns")]
+    Nanos,
+}
+
+impl TimeUnit {
+    fn formatter(&self) -> Expr {
+        match self {
+            TimeUnit::Seconds => parse_quote!(::custom_debug::timestamp_secs),
+            TimeUnit::Millis => parse_quote!(::custom_debug::timestamp_millis),
+            TimeUnit::Micros => parse_quote!(::custom_debug::timestamp_micros),
+            TimeUnit::Nanos => parse_quote!(::custom_debug::timestamp_nanos),
+        }
+    }
 }
 
 fn conflicting_skip_options_error() -> darling::Error {
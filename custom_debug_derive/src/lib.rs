@@ -1,43 +1,329 @@
+use darling::util::Override;
 use darling::FromMeta;
-use proc_macro2::TokenStream;
-use quote::quote;
-use syn::{Fields, Result};
+use proc_macro2::{Literal, TokenStream, TokenTree};
+use quote::{quote, ToTokens};
+use syn::{parse_quote, DeriveInput, Fields, Result};
 use synstructure::{decl_derive, AddBounds, BindingInfo, Structure, VariantInfo};
 
+use crate::container_attributes::ContainerAttributes;
 use crate::field_attributes::{DebugFormat, FieldAttributes, SkipMode};
 use crate::result_into_stream_ext::ResultIntoStreamExt;
 use crate::retain_ext::RetainExt;
 
+mod container_attributes;
+mod defmt;
 mod field_attributes;
+mod profiles;
 mod result_into_stream_ext;
 mod retain_ext;
-#[cfg(test)]
+mod serialize;
+#[cfg(all(test, not(feature = "minimal")))]
 mod tests;
+#[cfg(all(test, feature = "defmt", not(feature = "minimal")))]
+mod tests_defmt;
+#[cfg(all(test, feature = "minimal"))]
+mod tests_minimal;
+#[cfg(all(test, feature = "serde", not(feature = "minimal")))]
+mod tests_serialize;
+#[cfg(all(test, feature = "ufmt", not(feature = "minimal")))]
+mod tests_ufmt;
+#[cfg(all(test, feature = "valuable", not(feature = "minimal")))]
+mod tests_valuable;
+mod ufmt;
+mod valuable;
+mod visit;
+
+/// Generates an impl that types opt into with a container attribute.
+type GenerateImpl = fn(&DeriveInput) -> Result<TokenStream>;
 
 decl_derive!([Debug, attributes(debug)] => custom_debug_derive);
 
-fn custom_debug_derive(mut structure: Structure) -> Result<TokenStream> {
-    filter_out_skipped_fields(&mut structure)?;
+fn custom_debug_derive(structure: Structure) -> Result<TokenStream> {
+    let input = structure.ast();
+    let mut impls = generate_impl(
+        &profiles::for_profile(input, None)?,
+        quote! { ::core::fmt::Debug },
+        quote! { fmt },
+    )?;
+
+    for profile in profiles::profiles(input)? {
+        impls.extend(generate_impl(
+            &profiles::for_profile(input, Some(&profile))?,
+            quote! { ::custom_debug::DebugProfile<#profile> },
+            quote! { fmt_profile },
+        )?);
+    }
+
+    let container_attributes = parse_container_attributes(&structure)?;
+    let opt_in_impls: [(_, _, _, GenerateImpl); 4] = [
+        (
+            container_attributes.defmt,
+            cfg!(feature = "defmt"),
+            "Deriving `defmt::Format` requires the `defmt` feature",
+            defmt::generate_impl,
+        ),
+        (
+            container_attributes.valuable,
+            cfg!(feature = "valuable"),
+            "Deriving `valuable::Valuable` requires the `valuable` feature",
+            valuable::generate_impl,
+        ),
+        (
+            container_attributes.serialize,
+            cfg!(feature = "serde"),
+            "Deriving `SerializeDebug` requires the `serde` feature",
+            serialize::generate_impl,
+        ),
+        (
+            container_attributes.ufmt,
+            cfg!(feature = "ufmt"),
+            "Deriving `ufmt::uDebug` requires the `ufmt` feature",
+            ufmt::generate_impl,
+        ),
+    ];
+
+    for (flag, enabled, message, generate_impl) in opt_in_impls {
+        if !flag.is_present() {
+            continue;
+        }
+
+        if !enabled {
+            return Err(syn::Error::new(flag.span(), message));
+        }
+
+        impls.extend(generate_impl(&profiles::for_profile(input, None)?)?);
+    }
+
+    if container_attributes.visit.is_present() {
+        impls.extend(visit::generate_impl(&profiles::for_profile(input, None)?)?);
+    }
+
+    Ok(impls)
+}
+
+/// Generates an impl of `fmt::Debug` or a trait with the same signature.
+fn generate_impl(
+    input: &DeriveInput,
+    trait_path: TokenStream,
+    method: TokenStream,
+) -> Result<TokenStream> {
+    let mut structure = Structure::try_new(input)?;
+    let container_attributes = parse_container_attributes(&structure)?;
+
+    filter_out_skipped_fields(&mut structure, &container_attributes)?;
+
+    if cfg!(feature = "minimal") {
+        return Ok(generate_minimal_impl(
+            &mut structure,
+            &container_attributes,
+            trait_path,
+            method,
+        ));
+    }
+
+    // Profile impls format their fields with `Debug` as well
+    structure.add_bounds(AddBounds::None);
+    add_field_bounds(
+        &mut structure,
+        &container_attributes,
+        quote! { ::core::fmt::Debug },
+    )?;
+
+    let body = if container_attributes.compact_code.is_present() {
+        generate_compact_body(&structure, &container_attributes)
+    } else {
+        let match_arms = structure.each_variant(|variant| {
+            generate_match_arm_body(variant, &container_attributes).into_stream()
+        });
+
+        quote! {
+            match self {
+                #match_arms
+            }
+        }
+    };
+
+    let body = match container_attributes
+        .max_bytes
+        .map(Literal::usize_unsuffixed)
+    {
+        Some(max_bytes) => quote! {
+            ::custom_debug::__private::capped(fmt, #max_bytes, |fmt| {
+                #body
+            })
+        },
+        None => body,
+    };
+
+    // `shared` relies on the impl to tell when the format call ends
+    let max_depth = match &container_attributes.max_depth {
+        Some(Override::Explicit(max_depth)) => {
+            let max_depth = Literal::usize_unsuffixed(*max_depth);
 
-    structure.add_bounds(AddBounds::Fields);
+            Some(quote! { ::custom_debug::__private::max_depth!(#max_depth) })
+        }
+        Some(Override::Inherit) => Some(quote! { ::custom_debug::__private::max_depth!() }),
+        None if has_shared_fields(&structure, &container_attributes)? => {
+            Some(quote! { ::core::option::Option::None })
+        }
+        None => None,
+    };
 
-    let match_arms =
-        structure.each_variant(|variant| generate_match_arm_body(variant).into_stream());
+    let body = match max_depth {
+        Some(max_depth) => quote! {
+            ::custom_debug::__private::nested(fmt, #max_depth, |fmt| {
+                #body
+            })
+        },
+        None => body,
+    };
 
     Ok(structure.gen_impl(quote! {
-        gen impl ::core::fmt::Debug for @Self {
-            fn fmt(&self, fmt: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
-                match self {
-                    #match_arms
-                }
+        gen impl #trait_path for @Self {
+            fn #method(&self, fmt: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                #body
             }
         }
     }))
 }
 
-fn filter_out_skipped_fields(structure: &mut Structure) -> Result<()> {
+fn has_shared_fields(
+    structure: &Structure,
+    container_attributes: &ContainerAttributes,
+) -> Result<bool> {
+    for variant in structure.variants() {
+        for binding in variant.bindings() {
+            let field_attributes = parse_field_attributes(binding, container_attributes)?;
+
+            if let Some(with) = &field_attributes.debug_format.with {
+                if mentions_shared(with.to_token_stream()) {
+                    return Ok(true);
+                }
+            }
+        }
+    }
+
+    Ok(false)
+}
+
+/// Whether a formatter refers to `shared`, directly or through combinators like `some(shared)`.
+fn mentions_shared(tokens: TokenStream) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Ident(ident) => ident == "shared",
+        TokenTree::Group(group) => mentions_shared(group.stream()),
+        TokenTree::Punct(_) | TokenTree::Literal(_) => false,
+    })
+}
+
+/// Generates an impl that only shows the names of the type and variant,
+/// leaving out all field names and formatters.
+fn generate_minimal_impl(
+    structure: &mut Structure,
+    container_attributes: &ContainerAttributes,
+    trait_path: TokenStream,
+    method: TokenStream,
+) -> TokenStream {
+    // The fields are still bound, so they don't count as unused,
+    // but nothing requires them to implement `Debug`
+    structure.add_bounds(AddBounds::None);
+
+    let match_arms = structure.each_variant(|variant| {
+        let name = match variant.prefix {
+            Some(prefix) => format!("{}::{}", prefix, variant.ast().ident),
+            None => variant.ast().ident.to_string(),
+        };
+
+        match generate_minimal_references(variant, container_attributes) {
+            Ok(references) => quote! {
+                #references
+                #name
+            },
+            Err(err) => err.into_compile_error(),
+        }
+    });
+
+    structure.gen_impl(quote! {
+        gen impl #trait_path for @Self {
+            fn #method(&self, fmt: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                fmt.write_str(match self {
+                    #match_arms
+                })
+            }
+        }
+    })
+}
+
+/// Refers to the formatters and `skip_if` conditions of a variant in a closure that is never called,
+/// so they are still checked and don't count as unused, without ending up in the binary.
+fn generate_minimal_references(
+    variant: &VariantInfo,
+    container_attributes: &ContainerAttributes,
+) -> Result<TokenStream> {
+    let mut references = Vec::new();
+
+    for binding in variant.bindings() {
+        let field_attributes = parse_field_attributes(binding, container_attributes)?;
+
+        if !field_attributes.debug_format.is_plain() {
+            let debug_impl = generate_debug_impl(binding, &field_attributes.debug_format);
+
+            references.push(quote! { let _ = #debug_impl; });
+        }
+
+        if let SkipMode::Condition(condition) = &field_attributes.skip_mode {
+            references.push(quote! { let _ = #condition(#binding); });
+        }
+    }
+
+    if references.is_empty() {
+        return Ok(quote! {});
+    }
+
+    Ok(quote! {
+        let _ = || {
+            #(#references)*
+        };
+    })
+}
+
+/// Requires `trait_path` for generic fields that are shown as is
+/// and `Debug` for those that are formatted first.
+fn add_field_bounds(
+    structure: &mut Structure,
+    container_attributes: &ContainerAttributes,
+    trait_path: TokenStream,
+) -> Result<()> {
+    let mut predicates = Vec::new();
+
+    for variant in structure.variants() {
+        for binding in variant.bindings() {
+            if binding.referenced_ty_params().is_empty() {
+                continue;
+            }
+
+            let field_attributes = parse_field_attributes(binding, container_attributes)?;
+            let ty = &binding.ast().ty;
+
+            predicates.push(match field_attributes.debug_format.is_plain() {
+                true => parse_quote!(#ty: #trait_path),
+                false => parse_quote!(#ty: ::core::fmt::Debug),
+            });
+        }
+    }
+
+    for predicate in predicates {
+        structure.add_where_predicate(predicate);
+    }
+
+    Ok(())
+}
+
+fn filter_out_skipped_fields(
+    structure: &mut Structure,
+    container_attributes: &ContainerAttributes,
+) -> Result<()> {
     structure.try_retain(|binding| {
-        let field_attributes = parse_field_attributes(binding)?;
+        let field_attributes = parse_field_attributes(binding, container_attributes)?;
 
         Ok(field_attributes.skip_mode != SkipMode::Always)
     })?;
@@ -45,18 +331,26 @@ fn filter_out_skipped_fields(structure: &mut Structure) -> Result<()> {
     Ok(())
 }
 
-fn generate_match_arm_body(variant: &VariantInfo) -> Result<TokenStream> {
+fn generate_match_arm_body(
+    variant: &VariantInfo,
+    container_attributes: &ContainerAttributes,
+) -> Result<TokenStream> {
     let name = variant.ast().ident.to_string();
+    let type_name = type_name(variant);
     let debug_builder = match variant.ast().fields {
-        Fields::Named(_) | Fields::Unit => quote! { debug_struct },
-        Fields::Unnamed(_) => quote! { debug_tuple },
+        Fields::Named(_) | Fields::Unit if container_attributes.filter.is_present() => {
+            quote! { ::custom_debug::__private::debug_struct(fmt, #type_name, #name) }
+        }
+        Fields::Named(_) | Fields::Unit => quote! { fmt.debug_struct(#name) },
+        Fields::Unnamed(_) => quote! { fmt.debug_tuple(#name) },
     };
     let mut debug_builder_calls = Vec::new();
+    let mut has_levels = false;
 
     for binding in variant.bindings() {
-        let field_attributes = parse_field_attributes(binding)?;
+        let field_attributes = parse_field_attributes(binding, container_attributes)?;
 
-        let debug_builder_call = match &field_attributes.skip_mode {
+        let mut debug_builder_call = match &field_attributes.skip_mode {
             SkipMode::Default => generate_debug_builder_call(binding, &field_attributes)?,
             SkipMode::Condition(condition) => {
                 let debug_builder_call = generate_debug_builder_call(binding, &field_attributes)?;
@@ -67,14 +361,39 @@ fn generate_match_arm_body(variant: &VariantInfo) -> Result<TokenStream> {
                     }
                 }
             }
+            SkipMode::Sensitive => {
+                let debug_builder_call = generate_debug_builder_call(binding, &field_attributes)?;
+
+                quote! {
+                    if ::custom_debug::__private::REVEAL {
+                        #debug_builder_call
+                    }
+                }
+            }
             SkipMode::Always => quote! {},
         };
 
+        if let Some(level) = field_attributes.level.map(Literal::usize_unsuffixed) {
+            has_levels = true;
+            debug_builder_call = quote! {
+                if verbosity >= #level {
+                    #debug_builder_call
+                }
+            };
+        }
+
         debug_builder_calls.push(debug_builder_call);
     }
 
+    // Read before the builder borrows the formatter
+    let verbosity = match has_levels {
+        true => quote! { let verbosity = ::custom_debug::__private::verbosity(fmt); },
+        false => quote! {},
+    };
+
     Ok(quote! {
-        let mut debug_builder = fmt.#debug_builder(#name);
+        #verbosity
+        let mut debug_builder = #debug_builder;
 
         #(#debug_builder_calls)*
 
@@ -82,6 +401,89 @@ fn generate_match_arm_body(variant: &VariantInfo) -> Result<TokenStream> {
     })
 }
 
+/// Generates a body that looks up the names of unit-like variants in a static table.
+/// The output is identical to the one of [generate_match_arm_body].
+///
+/// Variants with fields keep their own builder calls: sharing those through helpers
+/// only saved a few percent in debug builds and grew release builds considerably,
+/// as the arms could no longer be merged.
+fn generate_compact_body(
+    structure: &Structure,
+    container_attributes: &ContainerAttributes,
+) -> TokenStream {
+    let mut unit_names = Vec::new();
+    let mut match_arms = Vec::new();
+
+    for variant in structure.variants() {
+        let pat = variant.pat();
+
+        if variant.bindings().is_empty() {
+            let index = unit_names.len();
+            unit_names.push(variant.ast().ident.to_string());
+
+            match_arms.push(quote! { #pat => #index, });
+        } else {
+            let body = generate_match_arm_body(variant, container_attributes).into_stream();
+
+            match_arms.push(quote! { #pat => return { #body }, });
+        }
+    }
+
+    if unit_names.is_empty() {
+        let match_arms = structure.each_variant(|variant| {
+            generate_match_arm_body(variant, container_attributes).into_stream()
+        });
+
+        return quote! {
+            match self {
+                #match_arms
+            }
+        };
+    }
+
+    quote! {
+        const NAMES: &[&str] = &[#(#unit_names),*];
+
+        let index = match self {
+            #(#match_arms)*
+        };
+
+        fmt.write_str(NAMES[index])
+    }
+}
+
+/// Name of the type a variant belongs to, as passed to field filters.
+fn type_name(variant: &VariantInfo) -> String {
+    variant.prefix.unwrap_or(variant.ast().ident).to_string()
+}
+
+/// Conditions under which impls other than `Debug` show a field, apart from its skip mode:
+/// Field filters for named fields of types with `filter`, and the current verbosity for fields with a `level`.
+fn generate_view_conditions(
+    binding: &BindingInfo,
+    field_attributes: &FieldAttributes,
+    container_attributes: &ContainerAttributes,
+    type_name: &str,
+) -> Vec<TokenStream> {
+    let mut conditions = Vec::new();
+
+    if container_attributes.filter.is_present() {
+        if let Some(field_name) = binding.ast().ident.as_ref().map(<_>::to_string) {
+            conditions.push(quote! {
+                ::custom_debug::__private::show_field(#type_name, #field_name)
+            });
+        }
+    }
+
+    if let Some(level) = field_attributes.level.map(Literal::usize_unsuffixed) {
+        conditions.push(quote! {
+            ::custom_debug::__private::current_verbosity() >= #level
+        });
+    }
+
+    conditions
+}
+
 fn generate_debug_builder_call(
     binding: &BindingInfo,
     field_attributes: &FieldAttributes,
@@ -103,32 +505,48 @@ fn generate_debug_builder_call(
 }
 
 fn generate_debug_impl(binding: &BindingInfo, debug_format: &DebugFormat) -> TokenStream {
-    match debug_format {
-        DebugFormat::Default => quote! { #binding },
-        DebugFormat::Format(format) => quote! { &format_args!(#format, #binding) },
-        DebugFormat::With(with) => quote! {
-            {
-                struct DebugWith<'a, T: 'a> {
-                    data: &'a T,
-                    fmt: fn(&T, &mut ::core::fmt::Formatter) -> ::core::fmt::Result,
-                }
+    let mut value = quote! { #binding };
 
-                impl<'a, T: 'a> ::core::fmt::Debug for DebugWith<'a, T> {
-                    fn fmt(&self, fmt: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
-                        (self.fmt)(self.data, fmt)
-                    }
-                }
+    if let Some(via) = &debug_format.via {
+        value = quote! { &#via(#value) };
+    }
 
-                &DebugWith {
-                    data: #binding,
-                    fmt: #with,
-                }
-            }
-        },
+    if let Some(with) = &debug_format.with {
+        value = quote! { &::custom_debug::DebugWith::new(#value, #with) };
+    }
+
+    if let Some(format) = &debug_format.format {
+        value = quote! { &format_args!(#format, #value) };
+    }
+
+    if debug_format.redact {
+        value = quote! { &::custom_debug::__private::Redacted(#value) };
     }
+
+    value
 }
 
-fn parse_field_attributes(binding: &BindingInfo<'_>) -> Result<FieldAttributes> {
+fn parse_container_attributes(structure: &Structure) -> Result<ContainerAttributes> {
+    let mut combined_container_attributes = ContainerAttributes::default();
+
+    for attr in &structure.ast().attrs {
+        if !attr.path().is_ident("debug") {
+            continue;
+        }
+
+        let container_attributes = ContainerAttributes::from_meta(&attr.meta)?;
+
+        combined_container_attributes =
+            combined_container_attributes.try_combine(container_attributes)?;
+    }
+
+    Ok(combined_container_attributes)
+}
+
+fn parse_field_attributes(
+    binding: &BindingInfo<'_>,
+    container_attributes: &ContainerAttributes,
+) -> Result<FieldAttributes> {
     let mut combined_field_attributes = FieldAttributes::default();
 
     for attr in &binding.ast().attrs {
@@ -141,5 +559,23 @@ fn parse_field_attributes(binding: &BindingInfo<'_>) -> Result<FieldAttributes>
         combined_field_attributes = combined_field_attributes.try_combine(field_attributes)?;
     }
 
-    Ok(combined_field_attributes)
+    Ok(combined_field_attributes.with_container_defaults(container_attributes, &binding.ast().ty))
+}
+
+/// Checks the impl a view generator derives for `input`, leaving out the `Debug` impl.
+#[cfg(all(
+    test,
+    any(
+        feature = "defmt",
+        feature = "serde",
+        feature = "ufmt",
+        feature = "valuable"
+    ),
+    not(feature = "minimal"),
+))]
+fn assert_generates(generate_impl: GenerateImpl, input: TokenStream, expected: TokenStream) {
+    let input = syn::parse2(input).unwrap();
+    let output = generate_impl(&input).unwrap();
+
+    assert_eq!(output.to_string(), expected.to_string());
 }
@@ -0,0 +1,164 @@
+//! Field attributes scoped to profiles, e.g. `#[debug(profile(log), skip)]`.
+//!
+//! Each profile is derived from a copy of the input in which every field
+//! keeps only the attributes that apply to that profile.
+
+use proc_macro2::Ident;
+use syn::punctuated::Punctuated;
+use syn::{parse_quote, Attribute, Data, DeriveInput, Field, Meta, Path, Token};
+
+/// The profiles named by field attributes, in order of appearance.
+pub fn profiles(ast: &DeriveInput) -> syn::Result<Vec<Path>> {
+    let mut profiles = Vec::new();
+
+    for field in fields(ast) {
+        for attr in debug_attrs(field) {
+            let (scope, _) = split_attr(attr)?;
+
+            for profile in scope.into_iter().flatten() {
+                if !profiles.contains(&profile) {
+                    profiles.push(profile);
+                }
+            }
+        }
+    }
+
+    Ok(profiles)
+}
+
+/// Copies `ast`, keeping the attributes of each field that apply to `profile`.
+///
+/// Fields with attributes scoped to `profile` use only those,
+/// all others use their unscoped attributes.
+/// Without a profile, only unscoped attributes are kept.
+pub fn for_profile(ast: &DeriveInput, profile: Option<&Path>) -> syn::Result<DeriveInput> {
+    let mut ast = ast.clone();
+
+    for field in fields_mut(&mut ast) {
+        let mut scoped = Vec::new();
+        let mut unscoped = Vec::new();
+        let mut other = Vec::new();
+
+        for attr in field.attrs.drain(..) {
+            if !attr.path().is_ident("debug") {
+                other.push(attr);
+                continue;
+            }
+
+            match split_attr(&attr)? {
+                (None, options) => unscoped.extend(options),
+                (Some(scope), options) if profile.is_some_and(|p| scope.contains(p)) => {
+                    scoped.extend(options)
+                }
+                (Some(_), _) => {}
+            }
+        }
+
+        let applied = match profile {
+            Some(_) if !scoped.is_empty() => scoped,
+            _ => unscoped,
+        };
+
+        field.attrs = other;
+        field.attrs.extend(applied);
+    }
+
+    Ok(ast)
+}
+
+/// Splits a `debug` attribute into the profiles it is scoped to
+/// and the attribute without the `profile(..)` option, if it has other options.
+fn split_attr(attr: &Attribute) -> syn::Result<(Option<Vec<Path>>, Option<Attribute>)> {
+    let Meta::List(_) = &attr.meta else {
+        return Ok((None, Some(attr.clone())));
+    };
+
+    let metas = attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?;
+    let mut scope = None;
+    let mut options = Punctuated::<Meta, Token![,]>::new();
+
+    for meta in metas {
+        match meta {
+            Meta::List(list) if list.path.is_ident("profile") => {
+                if scope.is_some() {
+                    return Err(syn::Error::new_spanned(list, "Duplicate profile option"));
+                }
+
+                let profiles =
+                    list.parse_args_with(Punctuated::<Path, Token![,]>::parse_terminated)?;
+                scope = Some(profiles.into_iter().map(profile_type).collect());
+            }
+            meta => options.push(meta),
+        }
+    }
+
+    if scope.is_none() {
+        return Ok((None, Some(attr.clone())));
+    }
+
+    let attr = match options.is_empty() {
+        true => None,
+        false => Some(parse_quote!(#[debug(#options)])),
+    };
+
+    Ok((scope, attr))
+}
+
+/// Maps a profile name like `log` or `access_log` to its type, `Log` or `AccessLog`.
+/// Names that aren't snake case are taken to be types already.
+fn profile_type(path: Path) -> Path {
+    let Some(ident) = path.get_ident() else {
+        return path;
+    };
+
+    let name = ident.to_string();
+
+    if !name.starts_with(|c: char| c.is_ascii_lowercase()) {
+        return path;
+    }
+
+    let name = name
+        .split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect::<String>();
+
+    Ident::new(&name, ident.span()).into()
+}
+
+fn debug_attrs(field: &Field) -> impl Iterator<Item = &Attribute> {
+    field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("debug"))
+}
+
+fn fields(ast: &DeriveInput) -> Vec<&Field> {
+    match &ast.data {
+        Data::Struct(data) => data.fields.iter().collect(),
+        Data::Enum(data) => data
+            .variants
+            .iter()
+            .flat_map(|variant| &variant.fields)
+            .collect(),
+        Data::Union(data) => data.fields.named.iter().collect(),
+    }
+}
+
+fn fields_mut(ast: &mut DeriveInput) -> Vec<&mut Field> {
+    match &mut ast.data {
+        Data::Struct(data) => data.fields.iter_mut().collect(),
+        Data::Enum(data) => data
+            .variants
+            .iter_mut()
+            .flat_map(|variant| &mut variant.fields)
+            .collect(),
+        Data::Union(data) => data.fields.named.iter_mut().collect(),
+    }
+}
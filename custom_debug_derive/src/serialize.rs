@@ -0,0 +1,182 @@
+//! `custom_debug::SerializeDebug` impls, serializing the fields shown by `Debug`.
+//!
+//! Fields are serialized with their own `Serialize` impl, unless they use a formatter,
+//! in which case their `Debug` output is serialized as a string.
+//! Fields hidden by field filters or their `level` are left out.
+
+use proc_macro2::{Literal, TokenStream};
+use quote::{format_ident, quote};
+use syn::{Data, DeriveInput, Fields, Result};
+use synstructure::{AddBounds, Structure, VariantInfo};
+
+use crate::container_attributes::ContainerAttributes;
+use crate::field_attributes::SkipMode;
+use crate::{
+    add_field_bounds, filter_out_skipped_fields, generate_debug_impl, generate_view_conditions,
+    parse_container_attributes, parse_field_attributes, type_name,
+};
+
+/// Generates an impl of `custom_debug::SerializeDebug`.
+pub fn generate_impl(input: &DeriveInput) -> Result<TokenStream> {
+    let mut structure = Structure::try_new(input)?;
+    let container_attributes = parse_container_attributes(&structure)?;
+
+    filter_out_skipped_fields(&mut structure, &container_attributes)?;
+    structure.add_bounds(AddBounds::None);
+    add_field_bounds(
+        &mut structure,
+        &container_attributes,
+        quote! { ::custom_debug::__private::serde::Serialize },
+    )?;
+
+    let is_enum = matches!(input.data, Data::Enum(_));
+    let mut match_arms = Vec::new();
+
+    for (index, variant) in structure.variants().iter().enumerate() {
+        let pat = variant.pat();
+        let index = is_enum.then_some(index as u32);
+        let body = generate_match_arm_body(variant, index, &container_attributes)?;
+
+        match_arms.push(quote! { #pat => { #body } });
+    }
+
+    Ok(structure.gen_impl(quote! {
+        gen impl ::custom_debug::SerializeDebug for @Self {
+            fn serialize_debug<__S>(
+                &self,
+                serializer: __S,
+            ) -> ::core::result::Result<__S::Ok, __S::Error>
+            where
+                __S: ::custom_debug::__private::serde::Serializer,
+            {
+                match self {
+                    #(#match_arms)*
+                }
+            }
+        }
+    }))
+}
+
+/// Serializes a variant, or a struct if there is no `variant_index`.
+fn generate_match_arm_body(
+    variant: &VariantInfo,
+    variant_index: Option<u32>,
+    container_attributes: &ContainerAttributes,
+) -> Result<TokenStream> {
+    let name = variant.ast().ident.to_string();
+    let type_name = type_name(variant);
+    let mut shows = Vec::new();
+    let mut lens = Vec::new();
+    let mut serialize_fields = Vec::new();
+
+    for binding in variant.bindings() {
+        let field_attributes = parse_field_attributes(binding, container_attributes)?;
+
+        let field_name = binding.ast().ident.as_ref().map(<_>::to_string);
+        let mut conditions = Vec::new();
+
+        match &field_attributes.skip_mode {
+            SkipMode::Default => {}
+            SkipMode::Condition(condition) => conditions.push(quote! { !#condition(#binding) }),
+            SkipMode::Sensitive => conditions.push(quote! { ::custom_debug::__private::REVEAL }),
+            SkipMode::Always => continue,
+        }
+
+        conditions.extend(generate_view_conditions(
+            binding,
+            &field_attributes,
+            container_attributes,
+            &type_name,
+        ));
+
+        let value = match field_attributes.debug_format.is_plain() {
+            true => quote! { #binding },
+            false => {
+                let debug_impl = generate_debug_impl(binding, &field_attributes.debug_format);
+
+                quote! { &::custom_debug::__private::DebugSerialize(#debug_impl) }
+            }
+        };
+
+        let field_name = field_name.map(|field_name| quote! { #field_name, });
+        let serialize_field = quote! { state.serialize_field(#field_name #value)?; };
+
+        if conditions.is_empty() {
+            lens.push(quote! { 1 });
+            serialize_fields.push(serialize_field);
+            continue;
+        }
+
+        let show = format_ident!("{}_shown", binding.binding);
+
+        shows.push(quote! { let #show = #(#conditions)&&*; });
+        lens.push(quote! { #show as usize });
+        serialize_fields.push(quote! {
+            if #show {
+                #serialize_field
+            }
+        });
+    }
+
+    let len = match lens.is_empty() {
+        true => quote! { 0 },
+        false => quote! { #(#lens)+* },
+    };
+    let serializer = quote! { ::custom_debug::__private::serde::Serializer };
+    let ser = quote! { ::custom_debug::__private::serde::ser };
+    let variant_index = variant_index.map(Literal::u32_unsuffixed);
+
+    let (state, state_trait) = match (&variant.ast().fields, &variant_index) {
+        (Fields::Unit, None) => {
+            return Ok(quote! { #serializer::serialize_unit_struct(serializer, #name) })
+        }
+        (Fields::Unit, Some(variant_index)) => {
+            return Ok(quote! {
+                #serializer::serialize_unit_variant(serializer, #type_name, #variant_index, #name)
+            })
+        }
+        (Fields::Named(_), None) => (
+            quote! { #serializer::serialize_struct(serializer, #name, #len) },
+            quote! { #ser::SerializeStruct },
+        ),
+        (Fields::Named(_), Some(variant_index)) => (
+            quote! {
+                #serializer::serialize_struct_variant(
+                    serializer,
+                    #type_name,
+                    #variant_index,
+                    #name,
+                    #len,
+                )
+            },
+            quote! { #ser::SerializeStructVariant },
+        ),
+        (Fields::Unnamed(_), None) => (
+            quote! { #serializer::serialize_tuple_struct(serializer, #name, #len) },
+            quote! { #ser::SerializeTupleStruct },
+        ),
+        (Fields::Unnamed(_), Some(variant_index)) => (
+            quote! {
+                #serializer::serialize_tuple_variant(
+                    serializer,
+                    #type_name,
+                    #variant_index,
+                    #name,
+                    #len,
+                )
+            },
+            quote! { #ser::SerializeTupleVariant },
+        ),
+    };
+
+    Ok(quote! {
+        use #state_trait as _;
+
+        #(#shows)*
+        let mut state = #state?;
+
+        #(#serialize_fields)*
+
+        state.end()
+    })
+}
@@ -1,6 +1,6 @@
 #![allow(clippy::match_single_binding)]
 use super::custom_debug_derive;
-use synstructure::test_derive;
+use synstructure::{test_derive, Structure};
 
 #[test]
 fn test_default_struct() {
@@ -81,23 +81,10 @@ fn test_with() {
                         match self {
                             Point { x: ref __binding_0, y: ref __binding_1, } => {
                                 let mut debug_builder = fmt.debug_struct("Point");
-                                debug_builder.field("x", {
-                                    struct DebugWith<'a, T: 'a> {
-                                        data: &'a T,
-                                        fmt: fn(&T, &mut ::core::fmt::Formatter) -> ::core::fmt::Result,
-                                    }
-
-                                    impl<'a, T: 'a> ::core::fmt::Debug for DebugWith<'a, T> {
-                                        fn fmt(&self, fmt: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
-                                            (self.fmt)(self.data, fmt)
-                                        }
-                                    }
-
-                                    &DebugWith {
-                                        data: __binding_0,
-                                        fmt: my_fmt,
-                                    }
-                                });
+                                debug_builder.field(
+                                    "x",
+                                    &::custom_debug::DebugWith::new(__binding_0, my_fmt)
+                                );
                                 debug_builder.field("y", __binding_1);
                                 debug_builder.finish()
                             }
@@ -318,3 +305,678 @@ fn test_bounds_on_fields_only() {
         no_build
     }
 }
+
+#[test]
+fn test_timestamp() {
+    test_derive! {
+        custom_debug_derive {
+            struct Event {
+                #[debug(timestamp)]
+                created: u64,
+                #[debug(timestamp(unit = "ms"))]
+                updated: u64,
+            }
+        }
+
+        expands to {
+            const _: () = {
+                impl ::core::fmt::Debug for Event {
+                    fn fmt(&self, fmt: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                        match self {
+                            Event { created: ref __binding_0, updated: ref __binding_1, } => {
+                                let mut debug_builder = fmt.debug_struct("Event");
+                                debug_builder.field(
+                                    "created",
+                                    &::custom_debug::DebugWith::new(__binding_0, ::custom_debug::timestamp_secs)
+                                );
+                                debug_builder.field(
+                                    "updated",
+                                    &::custom_debug::DebugWith::new(__binding_1, ::custom_debug::timestamp_millis)
+                                );
+                                debug_builder.finish()
+                            }
+                        }
+                    }
+                }
+            };
+        }
+
+        no_build
+    }
+}
+
+#[test]
+fn test_shorthand_conflict() {
+    let input = syn::parse_quote! {
+        struct Packet {
+            #[debug(ipv4, with = my_fmt)]
+            src: u32,
+        }
+    };
+
+    let error = custom_debug_derive(Structure::new(&input)).unwrap_err();
+
+    assert_eq!(error.to_string(), "Conflicting format options");
+}
+
+#[test]
+fn test_int_bytes() {
+    test_derive! {
+        custom_debug_derive {
+            struct Header {
+                #[debug(be)]
+                len: [u8; 2],
+                #[debug(le(raw))]
+                crc: [u8; 4],
+            }
+        }
+
+        expands to {
+            const _: () = {
+                impl ::core::fmt::Debug for Header {
+                    fn fmt(&self, fmt: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                        match self {
+                            Header { len: ref __binding_0, crc: ref __binding_1, } => {
+                                let mut debug_builder = fmt.debug_struct("Header");
+                                debug_builder.field(
+                                    "len",
+                                    &::custom_debug::DebugWith::new(__binding_0, ::custom_debug::be)
+                                );
+                                debug_builder.field(
+                                    "crc",
+                                    &::custom_debug::DebugWith::new(__binding_1, ::custom_debug::le_raw)
+                                );
+                                debug_builder.finish()
+                            }
+                        }
+                    }
+                }
+            };
+        }
+
+        no_build
+    }
+}
+
+#[test]
+fn test_base64() {
+    test_derive! {
+        custom_debug_derive {
+            struct Key {
+                #[debug(base64)]
+                public: Vec<u8>,
+                #[debug(base64(url, max = 8))]
+                signature: Vec<u8>,
+            }
+        }
+
+        expands to {
+            const _: () = {
+                impl ::core::fmt::Debug for Key {
+                    fn fmt(&self, fmt: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                        match self {
+                            Key { public: ref __binding_0, signature: ref __binding_1, } => {
+                                let mut debug_builder = fmt.debug_struct("Key");
+                                debug_builder.field(
+                                    "public",
+                                    &::custom_debug::DebugWith::new(__binding_0, ::custom_debug::base64)
+                                );
+                                debug_builder.field(
+                                    "signature",
+                                    &::custom_debug::DebugWith::new(__binding_1, ::custom_debug::base64url_truncated::<8>)
+                                );
+                                debug_builder.finish()
+                            }
+                        }
+                    }
+                }
+            };
+        }
+
+        no_build
+    }
+}
+
+#[test]
+fn test_compact_code() {
+    test_derive! {
+        custom_debug_derive {
+            #[debug(compact_code)]
+            enum Message {
+                Ping,
+                Data(#[debug(with = hexbuf)] Vec<u8>, u8),
+                Move { x: i32, #[debug(skip)] y: i32 },
+                Pong,
+            }
+        }
+
+        expands to {
+            const _: () = {
+                impl ::core::fmt::Debug for Message {
+                    fn fmt(&self, fmt: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                        const NAMES: &[&str] = &["Ping", "Pong"];
+
+                        let index = match self {
+                            Message::Ping => 0usize,
+                            Message::Data(ref __binding_0, ref __binding_1,) => return {
+                                let mut debug_builder = fmt.debug_tuple("Data");
+                                debug_builder.field(&::custom_debug::DebugWith::new(__binding_0, hexbuf));
+                                debug_builder.field(__binding_1);
+                                debug_builder.finish()
+                            },
+                            Message::Move { x: ref __binding_0, .. } => return {
+                                let mut debug_builder = fmt.debug_struct("Move");
+                                debug_builder.field("x", __binding_0);
+                                debug_builder.finish()
+                            },
+                            Message::Pong => 1usize,
+                        };
+
+                        fmt.write_str(NAMES[index])
+                    }
+                }
+            };
+        }
+
+        no_build
+    }
+}
+
+/// Compares the size of the code generated for a large protocol enum
+/// with and without `compact_code`, which is what rustc has to compile.
+#[test]
+fn test_compact_code_size() {
+    let variants = (0..300u32)
+        .map(|i| {
+            let unit = quote::format_ident!("Unit{}", i);
+            let tuple = quote::format_ident!("Tuple{}", i);
+            let named = quote::format_ident!("Named{}", i);
+
+            quote::quote! {
+                #unit,
+                #tuple(#[debug(with = hexbuf)] Vec<u8>, u32),
+                #named { id: u32, #[debug(format = "{} ms")] elapsed: u64 },
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let expansion_size = |container_attributes: proc_macro2::TokenStream| {
+        let input = syn::parse_quote! {
+            #container_attributes
+            enum Protocol {
+                #(#variants)*
+            }
+        };
+
+        let output = custom_debug_derive(Structure::new(&input)).unwrap();
+
+        output.to_string().len()
+    };
+
+    let default_size = expansion_size(quote::quote! {});
+    let compact_size = expansion_size(quote::quote! { #[debug(compact_code)] });
+
+    assert!(compact_size * 100 < default_size * 95);
+}
+
+#[test]
+fn test_with_expression() {
+    test_derive! {
+        custom_debug_derive {
+            struct Packets {
+                #[debug(with = each(hexbuf))]
+                payloads: Vec<Vec<u8>>,
+            }
+        }
+
+        expands to {
+            const _: () = {
+                impl ::core::fmt::Debug for Packets {
+                    fn fmt(&self, fmt: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                        match self {
+                            Packets { payloads: ref __binding_0, } => {
+                                let mut debug_builder = fmt.debug_struct("Packets");
+                                debug_builder.field(
+                                    "payloads",
+                                    &::custom_debug::DebugWith::new(__binding_0, each(hexbuf))
+                                );
+                                debug_builder.finish()
+                            }
+                        }
+                    }
+                }
+            };
+        }
+
+        no_build
+    }
+}
+
+#[test]
+fn test_with_options() {
+    test_derive! {
+        custom_debug_derive {
+            struct Frame {
+                #[debug(with(HexBuf, max = 32, ascii = true))]
+                payload: Vec<u8>,
+            }
+        }
+
+        expands to {
+            const _: () = {
+                impl ::core::fmt::Debug for Frame {
+                    fn fmt(&self, fmt: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                        match self {
+                            Frame { payload: ref __binding_0, } => {
+                                let mut debug_builder = fmt.debug_struct("Frame");
+                                debug_builder.field(
+                                    "payload",
+                                    &::custom_debug::DebugWith::new(__binding_0, {
+                                        #[allow(clippy::needless_update)]
+                                        let formatter = HexBuf {
+                                            max: 32,
+                                            ascii: true,
+                                            ..::core::default::Default::default()
+                                        };
+
+                                        formatter
+                                    })
+                                );
+                                debug_builder.finish()
+                            }
+                        }
+                    }
+                }
+            };
+        }
+
+        no_build
+    }
+}
+
+#[test]
+fn test_via_with_format() {
+    test_derive! {
+        custom_debug_derive {
+            struct Request {
+                #[debug(via = Duration::as_millis, with = hex, format = "{} ms")]
+                elapsed: Duration,
+                #[debug(via = str::len)]
+                body: String,
+            }
+        }
+
+        expands to {
+            const _: () = {
+                impl ::core::fmt::Debug for Request {
+                    fn fmt(&self, fmt: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                        match self {
+                            Request { elapsed: ref __binding_0, body: ref __binding_1, } => {
+                                let mut debug_builder = fmt.debug_struct("Request");
+                                debug_builder.field(
+                                    "elapsed",
+                                    &format_args!(
+                                        "{} ms",
+                                        &::custom_debug::DebugWith::new(&Duration::as_millis(__binding_0), hex)
+                                    )
+                                );
+                                debug_builder.field("body", &str::len(__binding_1));
+                                debug_builder.finish()
+                            }
+                        }
+                    }
+                }
+            };
+        }
+
+        no_build
+    }
+}
+
+#[test]
+fn test_sorted() {
+    test_derive! {
+        custom_debug_derive {
+            #[debug(sorted)]
+            struct Index {
+                by_name: HashMap<String, u32>,
+                #[debug(sorted(by_debug))]
+                tags: BTreeSet<Tag>,
+                ids: std::collections::HashSet<u32>,
+                order: Vec<u32>,
+            }
+        }
+
+        expands to {
+            const _: () = {
+                impl ::core::fmt::Debug for Index {
+                    fn fmt(&self, fmt: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                        match self {
+                            Index {
+                                by_name: ref __binding_0,
+                                tags: ref __binding_1,
+                                ids: ref __binding_2,
+                                order: ref __binding_3,
+                            } => {
+                                let mut debug_builder = fmt.debug_struct("Index");
+                                debug_builder.field(
+                                    "by_name",
+                                    &::custom_debug::DebugWith::new(__binding_0, ::custom_debug::sorted)
+                                );
+                                debug_builder.field(
+                                    "tags",
+                                    &::custom_debug::DebugWith::new(__binding_1, ::custom_debug::__private::sorted_by_debug!())
+                                );
+                                debug_builder.field(
+                                    "ids",
+                                    &::custom_debug::DebugWith::new(__binding_2, ::custom_debug::sorted)
+                                );
+                                debug_builder.field("order", __binding_3);
+                                debug_builder.finish()
+                            }
+                        }
+                    }
+                }
+            };
+        }
+
+        no_build
+    }
+}
+
+#[test]
+fn test_max_depth() {
+    test_derive! {
+        custom_debug_derive {
+            #[debug(max_depth = 3)]
+            struct Tree {
+                children: Vec<Tree>,
+            }
+        }
+
+        expands to {
+            const _: () = {
+                impl ::core::fmt::Debug for Tree {
+                    fn fmt(&self, fmt: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                        ::custom_debug::__private::nested(fmt, ::custom_debug::__private::max_depth!(3), |fmt| {
+                            match self {
+                                Tree { children: ref __binding_0, } => {
+                                    let mut debug_builder = fmt.debug_struct("Tree");
+                                    debug_builder.field("children", __binding_0);
+                                    debug_builder.finish()
+                                }
+                            }
+                        })
+                    }
+                }
+            };
+        }
+
+        no_build
+    }
+}
+
+#[test]
+fn test_shared_tracks_format_call() {
+    test_derive! {
+        custom_debug_derive {
+            struct Pair {
+                #[debug(shared)]
+                left: Rc<u8>,
+                right: u8,
+            }
+        }
+
+        expands to {
+            const _: () = {
+                impl ::core::fmt::Debug for Pair {
+                    fn fmt(&self, fmt: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                        ::custom_debug::__private::nested(fmt, ::core::option::Option::None, |fmt| {
+                            match self {
+                                Pair { left: ref __binding_0, right: ref __binding_1, } => {
+                                    let mut debug_builder = fmt.debug_struct("Pair");
+                                    debug_builder.field(
+                                        "left",
+                                        &::custom_debug::DebugWith::new(__binding_0, ::custom_debug::shared)
+                                    );
+                                    debug_builder.field("right", __binding_1);
+                                    debug_builder.finish()
+                                }
+                            }
+                        })
+                    }
+                }
+            };
+        }
+
+        no_build
+    }
+}
+
+#[test]
+fn test_max_bytes() {
+    test_derive! {
+        custom_debug_derive {
+            #[debug(max_bytes = 4096)]
+            struct Message {
+                text: String,
+            }
+        }
+
+        expands to {
+            const _: () = {
+                impl ::core::fmt::Debug for Message {
+                    fn fmt(&self, fmt: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                        ::custom_debug::__private::capped(fmt, 4096, |fmt| {
+                            match self {
+                                Message { text: ref __binding_0, } => {
+                                    let mut debug_builder = fmt.debug_struct("Message");
+                                    debug_builder.field("text", __binding_0);
+                                    debug_builder.finish()
+                                }
+                            }
+                        })
+                    }
+                }
+            };
+        }
+
+        no_build
+    }
+}
+
+#[test]
+fn test_profile() {
+    test_derive! {
+        custom_debug_derive {
+            struct Login {
+                user: String,
+                #[debug(profile(access_log), skip)]
+                password: String,
+            }
+        }
+
+        expands to {
+            const _: () = {
+                impl ::core::fmt::Debug for Login {
+                    fn fmt(&self, fmt: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                        match self {
+                            Login { user: ref __binding_0, password: ref __binding_1, } => {
+                                let mut debug_builder = fmt.debug_struct("Login");
+                                debug_builder.field("user", __binding_0);
+                                debug_builder.field("password", __binding_1);
+                                debug_builder.finish()
+                            }
+                        }
+                    }
+                }
+            };
+            const _: () = {
+                impl ::custom_debug::DebugProfile<AccessLog> for Login {
+                    fn fmt_profile(&self, fmt: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                        match self {
+                            Login { user: ref __binding_0, .. } => {
+                                let mut debug_builder = fmt.debug_struct("Login");
+                                debug_builder.field("user", __binding_0);
+                                debug_builder.finish()
+                            }
+                        }
+                    }
+                }
+            };
+        }
+
+        no_build
+    }
+}
+
+#[test]
+fn test_level() {
+    test_derive! {
+        custom_debug_derive {
+            struct Job {
+                id: u32,
+                #[debug(level = 2)]
+                retries: u32,
+            }
+        }
+
+        expands to {
+            const _: () = {
+                impl ::core::fmt::Debug for Job {
+                    fn fmt(&self, fmt: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                        match self {
+                            Job { id: ref __binding_0, retries: ref __binding_1, } => {
+                                let verbosity = ::custom_debug::__private::verbosity(fmt);
+                                let mut debug_builder = fmt.debug_struct("Job");
+                                debug_builder.field("id", __binding_0);
+
+                                if verbosity >= 2 {
+                                    debug_builder.field("retries", __binding_1);
+                                }
+
+                                debug_builder.finish()
+                            }
+                        }
+                    }
+                }
+            };
+        }
+
+        no_build
+    }
+}
+
+#[test]
+fn test_redact_and_sensitive() {
+    test_derive! {
+        custom_debug_derive {
+            struct Login {
+                #[debug(redact)]
+                password: String,
+                #[debug(skip(sensitive))]
+                token: String,
+            }
+        }
+
+        expands to {
+            const _: () = {
+                impl ::core::fmt::Debug for Login {
+                    fn fmt(&self, fmt: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                        match self {
+                            Login { password: ref __binding_0, token: ref __binding_1, } => {
+                                let mut debug_builder = fmt.debug_struct("Login");
+                                debug_builder.field(
+                                    "password",
+                                    &::custom_debug::__private::Redacted(__binding_0)
+                                );
+
+                                if ::custom_debug::__private::REVEAL {
+                                    debug_builder.field("token", __binding_1);
+                                }
+
+                                debug_builder.finish()
+                            }
+                        }
+                    }
+                }
+            };
+        }
+
+        no_build
+    }
+}
+
+#[test]
+fn test_visit() {
+    test_derive! {
+        custom_debug_derive {
+            #[debug(visit)]
+            enum Job {
+                Queued {
+                    id: u32,
+                    #[debug(level = 1)]
+                    retries: u32,
+                },
+                Failed(#[debug(skip)] u32, #[debug(with = hex)] u8),
+            }
+        }
+
+        expands to {
+            const _: () = {
+                impl ::core::fmt::Debug for Job {
+                    fn fmt(&self, fmt: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                        match self {
+                            Job::Queued { id: ref __binding_0, retries: ref __binding_1, } => {
+                                let verbosity = ::custom_debug::__private::verbosity(fmt);
+                                let mut debug_builder = fmt.debug_struct("Queued");
+
+                                debug_builder.field("id", __binding_0);
+
+                                if verbosity >= 1 {
+                                    debug_builder.field("retries", __binding_1);
+                                }
+
+                                debug_builder.finish()
+                            }
+                            Job::Failed(_, ref __binding_1,) => {
+                                let mut debug_builder = fmt.debug_tuple("Failed");
+
+                                debug_builder.field(&::custom_debug::DebugWith::new(__binding_1, hex));
+
+                                debug_builder.finish()
+                            }
+                        }
+                    }
+                }
+            };
+
+            const _: () = {
+                impl ::custom_debug::Visit for Job {
+                    fn visit(&self, visitor: &mut dyn ::custom_debug::Visitor) {
+                        match self {
+                            Job::Queued { id: ref __binding_0, retries: ref __binding_1, } => {
+                                visitor.variant("Job", "Queued");
+
+                                visitor.field("id", __binding_0);
+
+                                if ::custom_debug::__private::current_verbosity() >= 1 {
+                                    visitor.field("retries", __binding_1);
+                                }
+
+                                visitor.end();
+                            }
+                            Job::Failed(_, ref __binding_1,) => {
+                                visitor.variant("Job", "Failed");
+                                visitor.field("1", &::custom_debug::DebugWith::new(__binding_1, hex));
+                                visitor.end();
+                            }
+                        }
+                    }
+                }
+            };
+        }
+
+        no_build
+    }
+}
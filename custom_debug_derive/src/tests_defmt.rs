@@ -0,0 +1,204 @@
+use quote::quote;
+
+use crate::assert_generates;
+
+#[test]
+fn test_defmt() {
+    assert_generates(
+        crate::defmt::generate_impl,
+        quote! {
+            #[debug(filter, defmt)]
+            enum Reading<T> {
+                Sample {
+                    value: T,
+                    #[debug(with = hex)]
+                    id: u32,
+                    #[debug(skip)]
+                    raw: [u8; 4],
+                },
+                Error(#[debug(skip_if = Option::is_none)] Option<u8>),
+            }
+        },
+        quote! {
+            const _: () = {
+                use ::custom_debug::__private::defmt;
+
+                impl<T> ::custom_debug::__private::defmt::Format for Reading<T> where T: ::custom_debug::__private::defmt::Format {
+                    fn format(&self, fmt: ::custom_debug::__private::defmt::Formatter) {
+                        match self {
+                            Reading::Sample { value: ref __binding_0, id: ref __binding_1, .. } => {
+                                let mut first = true;
+
+                                ::custom_debug::__private::defmt::write!(fmt, "Sample");
+
+                                if ::custom_debug::__private::show_field("Reading", "value") {
+                                    if first {
+                                        ::custom_debug::__private::defmt::write!(fmt, " {{ value: {}", __binding_0);
+                                    } else {
+                                        ::custom_debug::__private::defmt::write!(fmt, ", value: {}", __binding_0);
+                                    }
+                                    first = false;
+                                }
+
+                                if ::custom_debug::__private::show_field("Reading", "id") {
+                                    if first {
+                                        ::custom_debug::__private::defmt::write!(
+                                            fmt,
+                                            " {{ id: {}",
+                                            ::custom_debug::__private::defmt::Debug2Format(
+                                                &::custom_debug::DebugWith::new(__binding_1, hex)
+                                            )
+                                        );
+                                    } else {
+                                        ::custom_debug::__private::defmt::write!(
+                                            fmt,
+                                            ", id: {}",
+                                            ::custom_debug::__private::defmt::Debug2Format(
+                                                &::custom_debug::DebugWith::new(__binding_1, hex)
+                                            )
+                                        );
+                                    }
+                                    first = false;
+                                }
+
+                                if !first {
+                                    ::custom_debug::__private::defmt::write!(fmt, " }}");
+                                }
+                            }
+                            Reading::Error(ref __binding_0,) => {
+                                let mut first = true;
+
+                                ::custom_debug::__private::defmt::write!(fmt, "Error");
+
+                                if !Option::is_none(__binding_0) {
+                                    if first {
+                                        ::custom_debug::__private::defmt::write!(fmt, "({}", __binding_0);
+                                    } else {
+                                        ::custom_debug::__private::defmt::write!(fmt, ", {}", __binding_0);
+                                    }
+                                    first = false;
+                                }
+
+                                if !first {
+                                    ::custom_debug::__private::defmt::write!(fmt, ")");
+                                }
+                            }
+                        }
+                    }
+                }
+            };
+        },
+    );
+}
+
+/// The format strings of all `defmt::write!` calls in the `Format` impl derived from `input`,
+/// as defmt parses them, with `{}` for each parameter.
+fn write_formats(input: proc_macro2::TokenStream) -> Vec<String> {
+    use defmt_parser::{Fragment, ParserMode};
+    use proc_macro2::{TokenStream, TokenTree};
+
+    fn collect(tokens: TokenStream, formats: &mut Vec<String>) {
+        let mut tokens = tokens.into_iter().peekable();
+
+        while let Some(token) = tokens.next() {
+            let TokenTree::Group(group) = token else {
+                if matches!(&token, TokenTree::Ident(ident) if ident == "write") {
+                    tokens.next();
+                    let Some(TokenTree::Group(args)) = tokens.next() else {
+                        panic!("expected arguments after `write!`");
+                    };
+                    let args = syn::parse::Parser::parse2(
+                        syn::punctuated::Punctuated::<syn::Expr, syn::Token![,]>::parse_terminated,
+                        args.stream(),
+                    )
+                    .unwrap();
+                    let syn::Expr::Lit(syn::ExprLit {
+                        lit: syn::Lit::Str(format),
+                        ..
+                    }) = &args[1]
+                    else {
+                        panic!("expected a format string");
+                    };
+                    let format = format.value();
+                    let fragments = defmt_parser::parse(&format, ParserMode::Strict).unwrap();
+                    let mut parameters = 0;
+                    let format = fragments
+                        .iter()
+                        .map(|fragment| match fragment {
+                            Fragment::Literal(literal) => literal.to_string(),
+                            Fragment::Parameter(_) => {
+                                parameters += 1;
+                                "{}".to_string()
+                            }
+                        })
+                        .collect::<String>();
+
+                    assert_eq!(parameters, args.len() - 2, "arguments of {:?}", format);
+                    formats.push(format);
+                }
+                continue;
+            };
+
+            collect(group.stream(), formats);
+        }
+    }
+
+    let input = syn::parse2(input).unwrap();
+    let mut formats = Vec::new();
+
+    collect(crate::defmt::generate_impl(&input).unwrap(), &mut formats);
+    formats
+}
+
+#[test]
+fn test_defmt_format_strings() {
+    let formats = write_formats(quote::quote! {
+        struct Point(i32, #[debug(with = hex)] i32, #[debug(skip)] i32);
+    });
+
+    assert_eq!(formats, ["Point({}, {})"]);
+
+    // Named fields and fields with a `level` can be hidden at runtime
+    let formats = write_formats(quote::quote! {
+        struct Point {
+            x: i32,
+            #[debug(level = 1)]
+            y: i32,
+        }
+    });
+
+    assert_eq!(
+        formats,
+        ["Point", " { x: {}", ", x: {}", " { y: {}", ", y: {}", " }"]
+    );
+
+    let formats = write_formats(quote::quote! {
+        enum Event {
+            Empty,
+            Moved(i32, #[debug(skip)] i32, i32),
+            Login {
+                user: u32,
+                #[debug(skip_if = Option::is_none)]
+                session: Option<u32>,
+                #[debug(redact)]
+                token: u64,
+            },
+        }
+    });
+
+    assert_eq!(
+        formats,
+        [
+            "Empty",
+            "Moved({}, {})",
+            "Login",
+            " { user: {}",
+            ", user: {}",
+            " { session: {}",
+            ", session: {}",
+            " { token: {}",
+            ", token: {}",
+            " }",
+        ]
+    );
+}
@@ -0,0 +1,45 @@
+use super::custom_debug_derive;
+use synstructure::test_derive;
+
+#[test]
+fn test_minimal() {
+    test_derive! {
+        custom_debug_derive {
+            enum Event<T> {
+                Started,
+                Progress(#[debug(with = hex)] u32),
+                Finished {
+                    result: T,
+                    #[debug(skip_if = Option::is_none)]
+                    note: Option<u8>,
+                },
+            }
+        }
+
+        expands to {
+            const _: () = {
+                impl<T> ::core::fmt::Debug for Event<T> {
+                    fn fmt(&self, fmt: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                        fmt.write_str(match self {
+                            Event::Started => { "Event::Started" }
+                            Event::Progress(ref __binding_0,) => {
+                                let _ = || {
+                                    let _ = &::custom_debug::DebugWith::new(__binding_0, hex);
+                                };
+                                "Event::Progress"
+                            }
+                            Event::Finished { result: ref __binding_0, note: ref __binding_1, } => {
+                                let _ = || {
+                                    let _ = Option::is_none(__binding_1);
+                                };
+                                "Event::Finished"
+                            }
+                        })
+                    }
+                }
+            };
+        }
+
+        no_build
+    }
+}
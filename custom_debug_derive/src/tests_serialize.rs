@@ -0,0 +1,77 @@
+use quote::quote;
+
+use crate::assert_generates;
+
+#[test]
+fn test_serialize() {
+    assert_generates(
+        crate::serialize::generate_impl,
+        quote! {
+            #[debug(filter, serialize)]
+            struct Reading<T> {
+                value: T,
+                #[debug(with = hex)]
+                id: u32,
+                #[debug(skip_if = Option::is_none)]
+                error: Option<u8>,
+            }
+        },
+        quote! {
+            const _: () = {
+                impl<T> ::custom_debug::SerializeDebug for Reading<T>
+                where
+                    T: ::custom_debug::__private::serde::Serialize
+                {
+                    fn serialize_debug<__S>(
+                        &self,
+                        serializer: __S,
+                    ) -> ::core::result::Result<__S::Ok, __S::Error>
+                    where
+                        __S: ::custom_debug::__private::serde::Serializer,
+                    {
+                        match self {
+                            Reading {
+                                value: ref __binding_0,
+                                id: ref __binding_1,
+                                error: ref __binding_2,
+                            } => {
+                                use ::custom_debug::__private::serde::ser::SerializeStruct as _;
+
+                                let __binding_0_shown =
+                                    ::custom_debug::__private::show_field("Reading", "value");
+                                let __binding_1_shown =
+                                    ::custom_debug::__private::show_field("Reading", "id");
+                                let __binding_2_shown = !Option::is_none(__binding_2)
+                                    && ::custom_debug::__private::show_field("Reading", "error");
+                                let mut state = ::custom_debug::__private::serde::Serializer::serialize_struct(
+                                    serializer,
+                                    "Reading",
+                                    __binding_0_shown as usize
+                                        + __binding_1_shown as usize
+                                        + __binding_2_shown as usize
+                                )?;
+
+                                if __binding_0_shown {
+                                    state.serialize_field("value", __binding_0)?;
+                                }
+                                if __binding_1_shown {
+                                    state.serialize_field(
+                                        "id",
+                                        &::custom_debug::__private::DebugSerialize(
+                                            &::custom_debug::DebugWith::new(__binding_1, hex)
+                                        )
+                                    )?;
+                                }
+                                if __binding_2_shown {
+                                    state.serialize_field("error", __binding_2)?;
+                                }
+
+                                state.end()
+                            }
+                        }
+                    }
+                }
+            };
+        },
+    );
+}
@@ -0,0 +1,153 @@
+use quote::quote;
+
+use crate::assert_generates;
+
+#[test]
+fn test_ufmt() {
+    assert_generates(
+        crate::ufmt::generate_impl,
+        quote! {
+            #[debug(filter, ufmt)]
+            enum Reading<T> {
+                Sample {
+                    value: T,
+                    #[debug(with = hexbuf, ufmt_with = ufmt_hexbuf)]
+                    id: u32,
+                    #[debug(skip)]
+                    raw: [u8; 4],
+                },
+                Error(#[debug(skip_if = Option::is_none)] Option<u8>),
+                Idle,
+            }
+        },
+        quote! {
+            const _: () = {
+                impl<T> ::ufmt::uDebug for Reading<T> where T: ::ufmt::uDebug {
+                    fn fmt<__W>(
+                        &self,
+                        f: &mut ::ufmt::Formatter<'_, __W>,
+                    ) -> ::core::result::Result<(), __W::Error>
+                    where
+                        __W: ::ufmt::uWrite + ?::core::marker::Sized,
+                    {
+                        match self {
+                            Reading::Sample { value: ref __binding_0, id: ref __binding_1, .. } => {
+                                let mut first = true;
+
+                                f.write_str("Sample")?;
+
+                                if ::custom_debug::__private::show_field("Reading", "value") {
+                                    f.write_str(if first { " { value: " } else { ", value: " })?;
+                                    ::ufmt::uDebug::fmt(__binding_0, f)?;
+                                    first = false;
+                                }
+
+                                if ::custom_debug::__private::show_field("Reading", "id") {
+                                    f.write_str(if first { " { id: " } else { ", id: " })?;
+                                    ufmt_hexbuf(__binding_1, f)?;
+                                    first = false;
+                                }
+
+                                if !first {
+                                    f.write_str(" }")?;
+                                }
+
+                                ::core::result::Result::Ok(())
+                            }
+                            Reading::Error(ref __binding_0,) => {
+                                let mut first = true;
+
+                                f.write_str("Error")?;
+
+                                if !Option::is_none(__binding_0) {
+                                    f.write_str(if first { "(" } else { ", " })?;
+                                    ::ufmt::uDebug::fmt(__binding_0, f)?;
+                                    first = false;
+                                }
+
+                                if !first {
+                                    f.write_str(")")?;
+                                }
+
+                                ::core::result::Result::Ok(())
+                            }
+                            Reading::Idle => {
+                                f.write_str("Idle")
+                            }
+                        }
+                    }
+                }
+            };
+        },
+    );
+}
+
+#[test]
+fn test_ufmt_redact_and_level() {
+    assert_generates(
+        crate::ufmt::generate_impl,
+        quote! {
+            #[debug(filter, ufmt)]
+            struct Login {
+                user: u32,
+                #[debug(redact)]
+                password: u64,
+                #[debug(level = 1)]
+                attempts: u8,
+            }
+        },
+        quote! {
+            const _: () = {
+                impl ::ufmt::uDebug for Login {
+                    fn fmt<__W>(
+                        &self,
+                        f: &mut ::ufmt::Formatter<'_, __W>,
+                    ) -> ::core::result::Result<(), __W::Error>
+                    where
+                        __W: ::ufmt::uWrite + ?::core::marker::Sized,
+                    {
+                        match self {
+                            Login { user: ref __binding_0, password: ref __binding_1, attempts: ref __binding_2, } => {
+                                let mut first = true;
+
+                                f.write_str("Login")?;
+
+                                if ::custom_debug::__private::show_field("Login", "user") {
+                                    f.write_str(if first { " { user: " } else { ", user: " })?;
+                                    ::ufmt::uDebug::fmt(__binding_0, f)?;
+                                    first = false;
+                                }
+
+                                if ::custom_debug::__private::show_field("Login", "password") {
+                                    f.write_str(if first { " { password: " } else { ", password: " })?;
+
+                                    if ::custom_debug::__private::REVEAL {
+                                        ::ufmt::uDebug::fmt(__binding_1, f)?;
+                                    } else {
+                                        f.write_str("<redacted>")?;
+                                    }
+
+                                    first = false;
+                                }
+
+                                if ::custom_debug::__private::show_field("Login", "attempts")
+                                    && ::custom_debug::__private::current_verbosity() >= 1
+                                {
+                                    f.write_str(if first { " { attempts: " } else { ", attempts: " })?;
+                                    ::ufmt::uDebug::fmt(__binding_2, f)?;
+                                    first = false;
+                                }
+
+                                if !first {
+                                    f.write_str(" }")?;
+                                }
+
+                                ::core::result::Result::Ok(())
+                            }
+                        }
+                    }
+                }
+            };
+        },
+    );
+}
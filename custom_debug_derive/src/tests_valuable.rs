@@ -0,0 +1,93 @@
+use quote::quote;
+
+use crate::assert_generates;
+
+#[test]
+fn test_valuable() {
+    assert_generates(
+        crate::valuable::generate_impl,
+        quote! {
+            #[debug(filter, valuable)]
+            enum Reading<T> {
+                Sample {
+                    value: T,
+                    #[debug(with = hex)]
+                    id: u32,
+                    #[debug(skip)]
+                    raw: [u8; 4],
+                },
+                Error(#[debug(skip_if = Option::is_none)] Option<u8>),
+            }
+        },
+        quote! {
+            const _: () = {
+                const VARIANT_0_FIELDS: &[::custom_debug::__private::valuable::NamedField<'static>] = &[
+                    ::custom_debug::__private::valuable::NamedField::new("value"),
+                    ::custom_debug::__private::valuable::NamedField::new("id")
+                ];
+
+                const VARIANTS: &[::custom_debug::__private::valuable::VariantDef<'static>] = &[
+                    ::custom_debug::__private::valuable::VariantDef::new(
+                        "Sample",
+                        ::custom_debug::__private::valuable::Fields::Named(VARIANT_0_FIELDS)
+                    ),
+                    ::custom_debug::__private::valuable::VariantDef::new("Error", ::custom_debug::__private::valuable::Fields::Unnamed(1))
+                ];
+
+                impl<T> ::custom_debug::__private::valuable::Valuable for Reading<T> where T: ::custom_debug::__private::valuable::Valuable {
+                    fn as_value(&self) -> ::custom_debug::__private::valuable::Value<'_> {
+                        ::custom_debug::__private::valuable::Value::Enumerable(self)
+                    }
+
+                    fn visit(&self, visit: &mut dyn ::custom_debug::__private::valuable::Visit) {
+                        match self {
+                            Reading::Sample { value: ref __binding_0, id: ref __binding_1, .. } => {
+                                let __binding_1_debug = ::custom_debug::__private::DebugStr::new(
+                                    &::custom_debug::DebugWith::new(__binding_1, hex)
+                                );
+
+                                visit.visit_named_fields(&::custom_debug::__private::valuable::NamedValues::new(
+                                    VARIANT_0_FIELDS,
+                                    &[
+                                        if ::custom_debug::__private::show_field("Reading", "value") {
+                                            ::custom_debug::__private::valuable::Valuable::as_value(__binding_0)
+                                        } else {
+                                            ::custom_debug::__private::valuable::Value::Unit
+                                        },
+                                        if ::custom_debug::__private::show_field("Reading", "id") {
+                                            ::custom_debug::__private::valuable::Value::String(__binding_1_debug.as_str())
+                                        } else {
+                                            ::custom_debug::__private::valuable::Value::Unit
+                                        }
+                                    ]
+                                ));
+                            }
+                            Reading::Error(ref __binding_0,) => {
+                                visit.visit_unnamed_fields(&[
+                                    if Option::is_none(__binding_0) {
+                                        ::custom_debug::__private::valuable::Value::Unit
+                                    } else {
+                                        ::custom_debug::__private::valuable::Valuable::as_value(__binding_0)
+                                    }
+                                ]);
+                            }
+                        }
+                    }
+                }
+
+                impl<T> ::custom_debug::__private::valuable::Enumerable for Reading<T> where T: ::custom_debug::__private::valuable::Valuable {
+                    fn definition(&self) -> ::custom_debug::__private::valuable::EnumDef<'_> {
+                        ::custom_debug::__private::valuable::EnumDef::new_static("Reading", VARIANTS)
+                    }
+
+                    fn variant(&self) -> ::custom_debug::__private::valuable::Variant<'_> {
+                        match self {
+                            Reading::Sample { value: ref __binding_0, id: ref __binding_1, .. } => ::custom_debug::__private::valuable::Variant::Static(&VARIANTS[0]),
+                            Reading::Error(ref __binding_0,) => ::custom_debug::__private::valuable::Variant::Static(&VARIANTS[1]),
+                        }
+                    }
+                }
+            };
+        },
+    );
+}
@@ -0,0 +1,258 @@
+//! `ufmt::uDebug` impls derived from the same attributes as `Debug`.
+//!
+//! Fields are written with their own `uDebug` impl, or with their `ufmt_with` formatter,
+//! which takes the value and a `ufmt::Formatter` instead of a `core::fmt::Formatter`.
+//! The output is always compact, and fields hidden by field filters or their `level` are left out.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse_quote, DeriveInput, Fields, Result};
+use synstructure::{AddBounds, BindingInfo, Structure, VariantInfo};
+
+use crate::container_attributes::ContainerAttributes;
+use crate::field_attributes::{FieldAttributes, SkipMode};
+use crate::result_into_stream_ext::ResultIntoStreamExt;
+use crate::{
+    filter_out_skipped_fields, generate_view_conditions, parse_container_attributes,
+    parse_field_attributes, type_name,
+};
+
+/// A field as it is written by the `uDebug` impl.
+struct Field {
+    /// Shows the field only if true
+    condition: Option<TokenStream>,
+    /// Field name and separator, without the leading `, `
+    label: String,
+    /// Writes the value, returning early on errors
+    write: TokenStream,
+}
+
+/// Generates an impl of `ufmt::uDebug`.
+pub fn generate_impl(input: &DeriveInput) -> Result<TokenStream> {
+    let mut structure = Structure::try_new(input)?;
+    let container_attributes = parse_container_attributes(&structure)?;
+
+    filter_out_skipped_fields(&mut structure, &container_attributes)?;
+    structure.add_bounds(AddBounds::None);
+
+    if cfg!(feature = "minimal") {
+        let match_arms = structure.each_variant(|variant| {
+            let name = match variant.prefix {
+                Some(prefix) => format!("{}::{}", prefix, variant.ast().ident),
+                None => variant.ast().ident.to_string(),
+            };
+
+            quote! { f.write_str(#name) }
+        });
+
+        return Ok(generate_trait_impl(&structure, match_arms));
+    }
+
+    add_field_bounds(&mut structure, &container_attributes)?;
+
+    let match_arms = structure.each_variant(|variant| {
+        generate_match_arm_body(variant, &container_attributes).into_stream()
+    });
+
+    Ok(generate_trait_impl(&structure, match_arms))
+}
+
+fn generate_trait_impl(structure: &Structure, match_arms: TokenStream) -> TokenStream {
+    structure.gen_impl(quote! {
+        gen impl ::ufmt::uDebug for @Self {
+            fn fmt<__W>(
+                &self,
+                f: &mut ::ufmt::Formatter<'_, __W>,
+            ) -> ::core::result::Result<(), __W::Error>
+            where
+                __W: ::ufmt::uWrite + ?::core::marker::Sized,
+            {
+                match self {
+                    #match_arms
+                }
+            }
+        }
+    })
+}
+
+/// Requires `uDebug` for generic fields that are written with their own impl.
+fn add_field_bounds(
+    structure: &mut Structure,
+    container_attributes: &ContainerAttributes,
+) -> Result<()> {
+    let mut predicates = Vec::new();
+
+    for variant in structure.variants() {
+        for binding in variant.bindings() {
+            if binding.referenced_ty_params().is_empty() {
+                continue;
+            }
+
+            let field_attributes = parse_field_attributes(binding, container_attributes)?;
+
+            if field_attributes.debug_format.via.is_none() && field_attributes.ufmt_with.is_none() {
+                let ty = &binding.ast().ty;
+
+                predicates.push(parse_quote!(#ty: ::ufmt::uDebug));
+            }
+        }
+    }
+
+    for predicate in predicates {
+        structure.add_where_predicate(predicate);
+    }
+
+    Ok(())
+}
+
+fn generate_match_arm_body(
+    variant: &VariantInfo,
+    container_attributes: &ContainerAttributes,
+) -> Result<TokenStream> {
+    let name = variant.ast().ident.to_string();
+    let type_name = type_name(variant);
+    let mut fields = Vec::new();
+
+    for binding in variant.bindings() {
+        let field_attributes = parse_field_attributes(binding, container_attributes)?;
+
+        let mut conditions = match &field_attributes.skip_mode {
+            SkipMode::Default => Vec::new(),
+            SkipMode::Condition(condition) => vec![quote! { !#condition(#binding) }],
+            SkipMode::Sensitive => vec![quote! { ::custom_debug::__private::REVEAL }],
+            SkipMode::Always => continue,
+        };
+
+        conditions.extend(generate_view_conditions(
+            binding,
+            &field_attributes,
+            container_attributes,
+            &type_name,
+        ));
+
+        let condition = match conditions.is_empty() {
+            true => None,
+            false => Some(quote! { #(#conditions)&&* }),
+        };
+
+        let label = match &binding.ast().ident {
+            Some(ident) => format!("{}: ", ident),
+            None => String::new(),
+        };
+
+        fields.push(Field {
+            condition,
+            label,
+            write: generate_write(binding, &field_attributes)?,
+        });
+    }
+
+    let (open, close) = match variant.ast().fields {
+        Fields::Named(_) | Fields::Unit => (" { ", " }"),
+        Fields::Unnamed(_) => ("(", ")"),
+    };
+
+    if fields.is_empty() {
+        return Ok(quote! { f.write_str(#name) });
+    }
+
+    // Without conditions, the separators are known up front
+    if fields.iter().all(|field| field.condition.is_none()) {
+        let writes = fields.iter().enumerate().map(|(index, field)| {
+            let Field { label, write, .. } = field;
+            let prefix = match index {
+                0 => format!("{}{}{}", name, open, label),
+                _ => format!(", {}", label),
+            };
+
+            quote! {
+                f.write_str(#prefix)?;
+                #write
+            }
+        });
+
+        return Ok(quote! {
+            #(#writes)*
+            f.write_str(#close)
+        });
+    }
+
+    let writes = fields.iter().map(|field| {
+        let Field {
+            condition,
+            label,
+            write,
+        } = field;
+        let first_prefix = format!("{}{}", open, label);
+        let prefix = format!(", {}", label);
+        let write = quote! {
+            f.write_str(if first { #first_prefix } else { #prefix })?;
+            #write
+            first = false;
+        };
+
+        match condition {
+            Some(condition) => quote! {
+                if #condition {
+                    #write
+                }
+            },
+            None => write,
+        }
+    });
+
+    Ok(quote! {
+        let mut first = true;
+
+        f.write_str(#name)?;
+
+        #(#writes)*
+
+        if !first {
+            f.write_str(#close)?;
+        }
+
+        ::core::result::Result::Ok(())
+    })
+}
+
+/// Generates statements that write the field and return early on errors.
+fn generate_write(
+    binding: &BindingInfo,
+    field_attributes: &FieldAttributes,
+) -> Result<TokenStream> {
+    let debug_format = &field_attributes.debug_format;
+
+    // `with` and `format` are for `core::fmt` only
+    let formatted = debug_format.with.is_some() || debug_format.format.is_some();
+
+    if formatted && field_attributes.ufmt_with.is_none() {
+        return Err(syn::Error::new_spanned(
+            binding.ast(),
+            "Deriving `ufmt::uDebug` for a field with `with` or `format` requires a `ufmt_with` formatter",
+        ));
+    }
+
+    let mut value = quote! { #binding };
+
+    if let Some(via) = &debug_format.via {
+        value = quote! { &#via(#value) };
+    }
+
+    let write = match &field_attributes.ufmt_with {
+        Some(ufmt_with) => quote! { #ufmt_with(#value, f)?; },
+        None => quote! { ::ufmt::uDebug::fmt(#value, f)?; },
+    };
+
+    if debug_format.redact {
+        return Ok(quote! {
+            if ::custom_debug::__private::REVEAL {
+                #write
+            } else {
+                f.write_str("<redacted>")?;
+            }
+        });
+    }
+
+    Ok(write)
+}
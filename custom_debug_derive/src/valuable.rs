@@ -0,0 +1,265 @@
+//! `valuable::Valuable` impls derived from the same attributes as `Debug`,
+//! along with `Structable` for structs and `Enumerable` for enums.
+//!
+//! Fields are passed as their own `Valuable` value, unless they use a formatter,
+//! in which case their `Debug` output is passed as a string.
+//! Fields hidden by `skip_if`, field filters or their `level` are passed as `Value::Unit`
+//! and sensitive fields are masked, so the definition of every variant is static.
+
+use proc_macro2::{Ident, Literal, TokenStream};
+use quote::{format_ident, quote};
+use syn::{Data, DeriveInput, Fields, Result};
+use synstructure::{AddBounds, BindingInfo, Structure, VariantInfo};
+
+use crate::container_attributes::ContainerAttributes;
+use crate::field_attributes::SkipMode;
+use crate::{
+    add_field_bounds, filter_out_skipped_fields, generate_debug_impl, generate_view_conditions,
+    parse_container_attributes, parse_field_attributes, type_name,
+};
+
+/// A variant as it is passed to a `valuable::Visit`.
+struct Variant {
+    name: String,
+    /// Names of the shown fields, if they are named
+    field_names: Option<Vec<String>>,
+    /// Output of formatters, computed before the values
+    locals: Vec<TokenStream>,
+    values: Vec<TokenStream>,
+}
+
+/// Generates impls of `valuable::Valuable` and `Structable` or `Enumerable`.
+pub fn generate_impl(input: &DeriveInput) -> Result<TokenStream> {
+    let mut structure = Structure::try_new(input)?;
+    let container_attributes = parse_container_attributes(&structure)?;
+
+    filter_out_skipped_fields(&mut structure, &container_attributes)?;
+    structure.add_bounds(AddBounds::None);
+    add_field_bounds(
+        &mut structure,
+        &container_attributes,
+        quote! { ::custom_debug::__private::valuable::Valuable },
+    )?;
+
+    let variants = structure
+        .variants()
+        .iter()
+        .map(|variant| generate_variant(variant, &container_attributes))
+        .collect::<Result<Vec<_>>>()?;
+    let name = input.ident.to_string();
+
+    if let Data::Enum(_) = input.data {
+        return Ok(generate_enum_impl(&structure, &name, &variants));
+    }
+
+    let variant = &variants[0];
+    let fields_const = format_ident!("FIELDS");
+    let fields = generate_fields(variant, &fields_const);
+    let visit_fields = generate_visit_fields(variant, &fields_const);
+    let pat = structure.variants()[0].pat();
+    let fields_def = variant.field_names.as_ref().map(|field_names| {
+        quote! {
+            const #fields_const: &[::custom_debug::__private::valuable::NamedField<'static>] = &[
+                #(::custom_debug::__private::valuable::NamedField::new(#field_names)),*
+            ];
+        }
+    });
+
+    Ok(structure.gen_impl(quote! {
+        #fields_def
+
+        gen impl ::custom_debug::__private::valuable::Valuable for @Self {
+            fn as_value(&self) -> ::custom_debug::__private::valuable::Value<'_> {
+                ::custom_debug::__private::valuable::Value::Structable(self)
+            }
+
+            fn visit(&self, visit: &mut dyn ::custom_debug::__private::valuable::Visit) {
+                match self {
+                    #pat => {
+                        #visit_fields
+                    }
+                }
+            }
+        }
+
+        gen impl ::custom_debug::__private::valuable::Structable for @Self {
+            fn definition(&self) -> ::custom_debug::__private::valuable::StructDef<'_> {
+                ::custom_debug::__private::valuable::StructDef::new_static(#name, #fields)
+            }
+        }
+    }))
+}
+
+fn generate_enum_impl(structure: &Structure, name: &str, variants: &[Variant]) -> TokenStream {
+    let mut fields_defs = Vec::new();
+    let mut variant_defs = Vec::new();
+    let mut visit_arms = Vec::new();
+    let mut variant_arms = Vec::new();
+
+    for (index, (variant_info, variant)) in structure.variants().iter().zip(variants).enumerate() {
+        let fields_const = format_ident!("VARIANT_{}_FIELDS", index);
+        let fields = generate_fields(variant, &fields_const);
+        let variant_name = &variant.name;
+        let static_variant = {
+            let index = Literal::usize_unsuffixed(index);
+
+            quote! { ::custom_debug::__private::valuable::Variant::Static(&VARIANTS[#index]) }
+        };
+        let visit_fields = generate_visit_fields(variant, &fields_const);
+        let pat = variant_info.pat();
+
+        if let Some(field_names) = &variant.field_names {
+            fields_defs.push(quote! {
+                const #fields_const: &[::custom_debug::__private::valuable::NamedField<'static>] = &[
+                    #(::custom_debug::__private::valuable::NamedField::new(#field_names)),*
+                ];
+            });
+        }
+
+        variant_defs.push(
+            quote! { ::custom_debug::__private::valuable::VariantDef::new(#variant_name, #fields) },
+        );
+        visit_arms.push(quote! { #pat => { #visit_fields } });
+        variant_arms.push(quote! { #pat => #static_variant, });
+    }
+
+    structure.gen_impl(quote! {
+        #(#fields_defs)*
+
+        const VARIANTS: &[::custom_debug::__private::valuable::VariantDef<'static>] = &[#(#variant_defs),*];
+
+        gen impl ::custom_debug::__private::valuable::Valuable for @Self {
+            fn as_value(&self) -> ::custom_debug::__private::valuable::Value<'_> {
+                ::custom_debug::__private::valuable::Value::Enumerable(self)
+            }
+
+            fn visit(&self, visit: &mut dyn ::custom_debug::__private::valuable::Visit) {
+                match self {
+                    #(#visit_arms)*
+                }
+            }
+        }
+
+        gen impl ::custom_debug::__private::valuable::Enumerable for @Self {
+            fn definition(&self) -> ::custom_debug::__private::valuable::EnumDef<'_> {
+                ::custom_debug::__private::valuable::EnumDef::new_static(#name, VARIANTS)
+            }
+
+            fn variant(&self) -> ::custom_debug::__private::valuable::Variant<'_> {
+                match self {
+                    #(#variant_arms)*
+                }
+            }
+        }
+    })
+}
+
+fn generate_variant(
+    variant: &VariantInfo,
+    container_attributes: &ContainerAttributes,
+) -> Result<Variant> {
+    let mut field_names = match variant.ast().fields {
+        Fields::Named(_) => Some(Vec::new()),
+        Fields::Unnamed(_) | Fields::Unit => None,
+    };
+    let type_name = type_name(variant);
+    let mut locals = Vec::new();
+    let mut values = Vec::new();
+
+    for binding in variant.bindings() {
+        let field_attributes = parse_field_attributes(binding, container_attributes)?;
+
+        let mut value = match field_attributes.debug_format.is_plain() {
+            true => quote! { ::custom_debug::__private::valuable::Valuable::as_value(#binding) },
+            false => {
+                let local = debug_str_ident(binding);
+                let debug_impl = generate_debug_impl(binding, &field_attributes.debug_format);
+
+                locals.push(quote! {
+                    let #local = ::custom_debug::__private::DebugStr::new(#debug_impl);
+                });
+
+                quote! { ::custom_debug::__private::valuable::Value::String(#local.as_str()) }
+            }
+        };
+
+        value = match &field_attributes.skip_mode {
+            SkipMode::Default => value,
+            SkipMode::Condition(condition) => quote! {
+                if #condition(#binding) {
+                    ::custom_debug::__private::valuable::Value::Unit
+                } else {
+                    #value
+                }
+            },
+            SkipMode::Sensitive => quote! {
+                if ::custom_debug::__private::REVEAL {
+                    #value
+                } else {
+                    ::custom_debug::__private::valuable::Value::String("<redacted>")
+                }
+            },
+            SkipMode::Always => continue,
+        };
+
+        let conditions =
+            generate_view_conditions(binding, &field_attributes, container_attributes, &type_name);
+
+        if !conditions.is_empty() {
+            value = quote! {
+                if #(#conditions)&&* {
+                    #value
+                } else {
+                    ::custom_debug::__private::valuable::Value::Unit
+                }
+            };
+        }
+
+        if let Some(field_names) = &mut field_names {
+            field_names.extend(binding.ast().ident.as_ref().map(<_>::to_string));
+        }
+
+        values.push(value);
+    }
+
+    Ok(Variant {
+        name: variant.ast().ident.to_string(),
+        field_names,
+        locals,
+        values,
+    })
+}
+
+fn debug_str_ident(binding: &BindingInfo) -> Ident {
+    format_ident!("{}_debug", binding.binding)
+}
+
+/// The `valuable::Fields` of a variant.
+fn generate_fields(variant: &Variant, fields_const: &Ident) -> TokenStream {
+    match variant.field_names {
+        Some(_) => quote! { ::custom_debug::__private::valuable::Fields::Named(#fields_const) },
+        None => {
+            let len = Literal::usize_unsuffixed(variant.values.len());
+
+            quote! { ::custom_debug::__private::valuable::Fields::Unnamed(#len) }
+        }
+    }
+}
+
+/// Passes the fields of a variant to `visit`.
+fn generate_visit_fields(variant: &Variant, fields_const: &Ident) -> TokenStream {
+    let Variant { locals, values, .. } = variant;
+    let visit = match variant.field_names {
+        Some(_) => quote! {
+            visit.visit_named_fields(&::custom_debug::__private::valuable::NamedValues::new(#fields_const, &[#(#values),*]))
+        },
+        None => quote! {
+            visit.visit_unnamed_fields(&[#(#values),*])
+        },
+    };
+
+    quote! {
+        #(#locals)*
+        #visit;
+    }
+}
@@ -0,0 +1,113 @@
+//! `custom_debug::Visit` impls, passing the fields shown by `Debug` to a visitor.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Result};
+use synstructure::{AddBounds, Structure, VariantInfo};
+
+use crate::container_attributes::ContainerAttributes;
+use crate::field_attributes::SkipMode;
+use crate::result_into_stream_ext::ResultIntoStreamExt;
+use crate::{
+    add_field_bounds, filter_out_skipped_fields, generate_debug_impl, generate_view_conditions,
+    parse_container_attributes, parse_field_attributes, type_name,
+};
+
+/// Generates an impl of `custom_debug::Visit`.
+pub fn generate_impl(input: &DeriveInput) -> Result<TokenStream> {
+    let mut structure = Structure::try_new(input)?;
+    let container_attributes = parse_container_attributes(&structure)?;
+
+    filter_out_skipped_fields(&mut structure, &container_attributes)?;
+    structure.add_bounds(AddBounds::None);
+    add_field_bounds(
+        &mut structure,
+        &container_attributes,
+        quote! { ::core::fmt::Debug },
+    )?;
+
+    let is_enum = matches!(input.data, Data::Enum(_));
+    let match_arms = structure.each_variant(|variant| {
+        generate_match_arm_body(variant, is_enum, &container_attributes).into_stream()
+    });
+
+    Ok(structure.gen_impl(quote! {
+        gen impl ::custom_debug::Visit for @Self {
+            fn visit(&self, visitor: &mut dyn ::custom_debug::Visitor) {
+                match self {
+                    #match_arms
+                }
+            }
+        }
+    }))
+}
+
+fn generate_match_arm_body(
+    variant: &VariantInfo,
+    is_enum: bool,
+    container_attributes: &ContainerAttributes,
+) -> Result<TokenStream> {
+    let name = variant.ast().ident.to_string();
+    let type_name = type_name(variant);
+    let start = match is_enum {
+        true => quote! { visitor.variant(#type_name, #name); },
+        false => quote! { visitor.struct_start(#name); },
+    };
+    let mut field_calls = Vec::new();
+
+    for binding in variant.bindings() {
+        let field_attributes = parse_field_attributes(binding, container_attributes)?;
+        let value = generate_debug_impl(binding, &field_attributes.debug_format);
+
+        let field_call = match &binding.ast().ident {
+            Some(ident) => {
+                let field_name = ident.to_string();
+
+                quote! { visitor.field(#field_name, #value); }
+            }
+            None => {
+                // Named by the position among all fields, including skipped ones
+                let index = variant
+                    .ast()
+                    .fields
+                    .iter()
+                    .position(|field| core::ptr::eq(field, binding.ast()))
+                    .unwrap_or_default()
+                    .to_string();
+
+                quote! { visitor.field(#index, #value); }
+            }
+        };
+
+        let mut conditions = match &field_attributes.skip_mode {
+            SkipMode::Default => Vec::new(),
+            SkipMode::Condition(condition) => vec![quote! { !#condition(#binding) }],
+            SkipMode::Sensitive => vec![quote! { ::custom_debug::__private::REVEAL }],
+            SkipMode::Always => continue,
+        };
+
+        conditions.extend(generate_view_conditions(
+            binding,
+            &field_attributes,
+            container_attributes,
+            &type_name,
+        ));
+
+        field_calls.push(match conditions.is_empty() {
+            true => field_call,
+            false => quote! {
+                if #(#conditions)&&* {
+                    #field_call
+                }
+            },
+        });
+    }
+
+    Ok(quote! {
+        #start
+
+        #(#field_calls)*
+
+        visitor.end();
+    })
+}
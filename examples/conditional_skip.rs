@@ -1,14 +1,12 @@
 #![allow(clippy::disallowed_names)]
-use core::fmt;
-
-use custom_debug::Debug;
+use custom_debug::{some_or, Debug};
 
 #[derive(Debug)]
 struct Foo {
     x: i32,
     #[debug(
         skip_if = Option::is_none,
-        with = strip_some,
+        with = some_or(""),
     )]
     y: Option<i32>,
     z: i32,
@@ -28,11 +26,3 @@ fn main() {
     println!("With `y = Some(123)`:");
     println!("{:#?}", foo);
 }
-
-fn strip_some<T: fmt::Debug>(value: &Option<T>, f: &mut fmt::Formatter) -> fmt::Result {
-    if let Some(value) = value {
-        value.fmt(f)?;
-    }
-
-    Ok(())
-}
@@ -0,0 +1,18 @@
+use custom_debug::Debug;
+
+#[derive(Debug)]
+struct Event {
+    #[debug(timestamp)]
+    created: u64,
+    #[debug(timestamp(unit = "ms"))]
+    updated: u64,
+}
+
+fn main() {
+    let event = Event {
+        created: 1_792_324_800,
+        updated: 1_792_324_800_250,
+    };
+
+    println!("{:#?}", event);
+}
@@ -0,0 +1,211 @@
+//! Runtime helpers for derived impls. Not part of the public API.
+
+use core::fmt;
+
+/// Starts formatting a variant with named fields of the type `type_name`.
+pub fn debug_struct<'a, 'b>(
+    fmt: &'a mut fmt::Formatter<'b>,
+    type_name: &'static str,
+    name: &str,
+) -> DebugStruct<'a, 'b> {
+    DebugStruct {
+        type_name,
+        debug_builder: fmt.debug_struct(name),
+    }
+}
+
+/// [fmt::DebugStruct] that only shows the fields accepted by the field filters (requires `std`).
+pub struct DebugStruct<'a, 'b> {
+    type_name: &'static str,
+    debug_builder: fmt::DebugStruct<'a, 'b>,
+}
+
+impl DebugStruct<'_, '_> {
+    #[inline]
+    pub fn field(&mut self, name: &str, value: &dyn fmt::Debug) -> &mut Self {
+        if !show_field(self.type_name, name) {
+            return self;
+        }
+
+        self.debug_builder.field(name, value);
+        self
+    }
+
+    pub fn finish(&mut self) -> fmt::Result {
+        self.debug_builder.finish()
+    }
+}
+
+/// Runs the body of a derived impl one level deeper,
+/// or elides it as `{..}` beyond the depth limit (requires `std`).
+#[inline(always)]
+pub fn nested(
+    fmt: &mut fmt::Formatter,
+    max_depth: Option<usize>,
+    body: impl FnOnce(&mut fmt::Formatter) -> fmt::Result,
+) -> fmt::Result {
+    #[cfg(feature = "std")]
+    return crate::depth::nested(fmt, max_depth, body);
+
+    #[cfg(not(feature = "std"))]
+    {
+        let _ = max_depth;
+        body(fmt)
+    }
+}
+
+/// Depth limit of a type with `max_depth`, or an error without `std`.
+#[cfg(feature = "std")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __max_depth {
+    () => {
+        ::core::option::Option::None
+    };
+    ($max_depth:literal) => {
+        ::core::option::Option::Some($max_depth)
+    };
+}
+
+/// Depth limit of a type with `max_depth`, or an error without `std`.
+#[cfg(not(feature = "std"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __max_depth {
+    ($($max_depth:literal)?) => {
+        ::core::compile_error!("max_depth requires the std feature")
+    };
+}
+
+pub use crate::__max_depth as max_depth;
+
+/// Runs the body of a derived impl with at most `max` bytes of output.
+pub fn capped(
+    fmt: &mut fmt::Formatter,
+    max: usize,
+    body: impl Fn(&mut fmt::Formatter) -> fmt::Result,
+) -> fmt::Result {
+    fmt::Debug::fmt(&crate::Capped::new(&crate::debug_fn(body), max), fmt)
+}
+
+/// Verbosity for the fields with a `level` attribute:
+/// The one set by `Verbose` (requires `std`), or 1 for `{:#?}` and 0 otherwise.
+pub fn verbosity(fmt: &fmt::Formatter) -> usize {
+    #[cfg(feature = "std")]
+    if let Some(verbosity) = crate::verbose::verbosity() {
+        return verbosity;
+    }
+
+    fmt.alternate() as usize
+}
+
+/// Verbosity for the fields with a `level` attribute outside of formatting:
+/// The one set by `Verbose` (requires `std`), or 0.
+pub fn current_verbosity() -> usize {
+    #[cfg(feature = "std")]
+    if let Some(verbosity) = crate::verbose::verbosity() {
+        return verbosity;
+    }
+
+    0
+}
+
+/// Whether redacted and sensitive fields are shown.
+pub const REVEAL: bool = cfg!(custom_debug_reveal);
+
+/// Output of a redacted field.
+pub struct Redacted<'a>(pub &'a dyn fmt::Debug);
+
+impl fmt::Debug for Redacted<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match REVEAL {
+            true => self.0.fmt(f),
+            false => f.write_str("<redacted>"),
+        }
+    }
+}
+
+/// Output of a field formatted with `Debug`, for structured values like [valuable::Value].
+///
+/// Without `alloc`, output beyond 256 bytes is dropped.
+#[cfg(feature = "valuable")]
+pub struct DebugStr {
+    #[cfg(feature = "alloc")]
+    text: alloc::string::String,
+    #[cfg(not(feature = "alloc"))]
+    text: crate::ArrayString<256>,
+}
+
+#[cfg(feature = "valuable")]
+impl DebugStr {
+    pub fn new(value: &dyn fmt::Debug) -> Self {
+        use core::fmt::Write;
+
+        let mut debug_str = Self {
+            text: Default::default(),
+        };
+        // Errors come from the value's impl, what was written up to that point is kept
+        let _ = write!(debug_str.text, "{:?}", value);
+
+        debug_str
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+}
+
+#[cfg(feature = "serde")]
+pub use serde;
+
+#[cfg(feature = "defmt")]
+pub use defmt;
+
+#[cfg(feature = "valuable")]
+pub use valuable;
+
+/// Serializes the output of a field formatted with `Debug` as a string.
+#[cfg(feature = "serde")]
+pub struct DebugSerialize<'a>(pub &'a dyn fmt::Debug);
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for DebugSerialize<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&format_args!("{:?}", self.0))
+    }
+}
+
+/// Whether the field filters (requires `std`) show the field `field_name` of `type_name`.
+#[inline]
+pub fn show_field(type_name: &str, field_name: &str) -> bool {
+    #[cfg(feature = "std")]
+    return crate::filter::show_field(type_name, field_name);
+
+    #[cfg(not(feature = "std"))]
+    {
+        let _ = (type_name, field_name);
+        true
+    }
+}
+
+/// Path of [sorted_by_debug](crate::sorted_by_debug), or an error without `alloc`.
+#[cfg(feature = "alloc")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __sorted_by_debug {
+    () => {
+        $crate::sorted_by_debug
+    };
+}
+
+/// Path of [sorted_by_debug](crate::sorted_by_debug), or an error without `alloc`.
+#[cfg(not(feature = "alloc"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __sorted_by_debug {
+    () => {
+        ::core::compile_error!("sorted(by_debug) requires the alloc feature")
+    };
+}
+
+pub use crate::__sorted_by_debug as sorted_by_debug;
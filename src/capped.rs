@@ -0,0 +1,202 @@
+use core::fmt::{self, Write};
+
+/// Appended to output that was cut off.
+const MARKER: &str = "…[truncated]";
+/// Appended instead of [MARKER] if `max` is too small for it.
+const SHORT_MARKER: &str = "…";
+
+/// Formats a value with at most `max` bytes of output.
+///
+/// Longer output is cut off at a character boundary and ends with `…[truncated]`,
+/// with the marker counting towards `max`. Formatting still succeeds in that case.
+/// If `max` is too small for the marker, the output ends with `…` instead,
+/// or with dots for less than 3 bytes.
+///
+/// The width, precision, alignment and the `#` and `0` flags are forwarded to the value.
+/// The fill character is forwarded if it is one of ` 0*-_.` and replaced by a space otherwise,
+/// as std has no stable way to format with a fill character that is only known at runtime.
+///
+/// ```
+/// use custom_debug::Capped;
+///
+/// let values = [1, 2, 3, 4, 5, 6, 7, 8, 9];
+///
+/// assert_eq!(format!("{:?}", Capped::new(&values, 25)), "[1, 2, 3, 4…[truncated]");
+/// ```
+pub struct Capped<'a, T: ?Sized> {
+    value: &'a T,
+    max: usize,
+}
+
+impl<'a, T: ?Sized> Capped<'a, T> {
+    pub fn new(value: &'a T, max: usize) -> Self {
+        Self { value, max }
+    }
+}
+
+impl<T: fmt::Debug + ?Sized> fmt::Debug for Capped<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let marker = match self.max {
+            max if max >= MARKER.len() => MARKER,
+            max if max >= SHORT_MARKER.len() => SHORT_MARKER,
+            max => &"..."[..max],
+        };
+        let mut writer = CappedWriter {
+            f,
+            direct: self.max - marker.len(),
+            reserved: marker.len(),
+            held: [0; MARKER.len() + 3],
+            held_len: 0,
+            held_max: 0,
+            truncated: false,
+        };
+
+        let options = Options::of(writer.f);
+        let result = write_with_options(&mut writer, &self.value, &options);
+
+        let CappedWriter {
+            f,
+            held,
+            held_len,
+            truncated,
+            ..
+        } = writer;
+
+        if truncated {
+            return f.write_str(marker);
+        }
+
+        result?;
+
+        // Only whole strings are held back
+        f.write_str(core::str::from_utf8(&held[..held_len]).map_err(|_| fmt::Error)?)
+    }
+}
+
+/// The options of a [Formatter](fmt::Formatter) that are forwarded to the value.
+struct Options {
+    alternate: bool,
+    zero_pad: bool,
+    width: usize,
+    precision: Option<usize>,
+    align: Option<fmt::Alignment>,
+    fill: char,
+}
+
+impl Options {
+    fn of(f: &fmt::Formatter) -> Self {
+        Self {
+            alternate: f.alternate(),
+            zero_pad: f.sign_aware_zero_pad(),
+            // A width of 0 never pads
+            width: f.width().unwrap_or(0),
+            precision: f.precision(),
+            align: f.align(),
+            fill: f.fill(),
+        }
+    }
+}
+
+/// Formats `value` with `options`.
+///
+/// Format specs are fixed at compile time, so every combination gets its own.
+/// Not generic, to only have them once in the binary.
+fn write_with_options(
+    writer: &mut dyn Write,
+    value: &dyn fmt::Debug,
+    options: &Options,
+) -> fmt::Result {
+    let width = options.width;
+
+    macro_rules! with_precision {
+        ($spec:expr) => {
+            match options.precision {
+                None => write!(writer, concat!("{0:", $spec, "1$?}"), value, width),
+                Some(precision) => write!(
+                    writer,
+                    concat!("{0:", $spec, "1$.2$?}"),
+                    value, width, precision
+                ),
+            }
+        };
+    }
+
+    macro_rules! with_flags {
+        ($align:expr) => {
+            match (options.alternate, options.zero_pad) {
+                (false, false) => with_precision!($align),
+                (true, false) => with_precision!(concat!($align, "#")),
+                (false, true) => with_precision!(concat!($align, "0")),
+                (true, true) => with_precision!(concat!($align, "#0")),
+            }
+        };
+    }
+
+    macro_rules! with_fill {
+        ($align:literal) => {
+            match options.fill {
+                '0' => with_flags!(concat!("0", $align)),
+                '*' => with_flags!(concat!("*", $align)),
+                '-' => with_flags!(concat!("-", $align)),
+                '_' => with_flags!(concat!("_", $align)),
+                '.' => with_flags!(concat!(".", $align)),
+                _ => with_flags!(concat!(" ", $align)),
+            }
+        };
+    }
+
+    match options.align {
+        None => with_flags!(""),
+        Some(fmt::Alignment::Left) => with_fill!("<"),
+        Some(fmt::Alignment::Center) => with_fill!("^"),
+        Some(fmt::Alignment::Right) => with_fill!(">"),
+    }
+}
+
+/// Writes the first bytes directly and holds back the last ones
+/// until it is clear whether they have to make room for the [MARKER].
+struct CappedWriter<'a, 'b> {
+    f: &'a mut fmt::Formatter<'b>,
+    /// Bytes that can still be written directly
+    direct: usize,
+    /// Bytes reserved for the marker
+    reserved: usize,
+    held: [u8; MARKER.len() + 3],
+    held_len: usize,
+    held_max: usize,
+    truncated: bool,
+}
+
+impl Write for CappedWriter<'_, '_> {
+    fn write_str(&mut self, mut s: &str) -> fmt::Result {
+        if self.held_len == 0 {
+            let mut len = self.direct.min(s.len());
+
+            while !s.is_char_boundary(len) {
+                len -= 1;
+            }
+
+            self.f.write_str(&s[..len])?;
+            self.direct -= len;
+            s = &s[len..];
+
+            if s.is_empty() {
+                return Ok(());
+            }
+
+            // Less than a character of direct output is left
+            self.held_max = self.direct + self.reserved;
+            self.direct = 0;
+        }
+
+        if self.held_len + s.len() > self.held_max {
+            self.truncated = true;
+            return Err(fmt::Error);
+        }
+
+        self.held[self.held_len..][..s.len()].copy_from_slice(s.as_bytes());
+        self.held_len += s.len();
+
+        Ok(())
+    }
+}
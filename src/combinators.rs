@@ -0,0 +1,123 @@
+//! Formatters that apply other formatters inside of container types.
+//!
+//! ```
+//! use custom_debug::{each, hexbuf, Debug};
+//!
+//! #[derive(Debug)]
+//! struct Packets {
+//!     #[debug(with = each(hexbuf))]
+//!     payloads: Vec<Vec<u8>>,
+//! }
+//! ```
+use core::fmt;
+
+use crate::{debug_fn, DebugFormatter};
+
+/// Formats every item of a list-like container with `f`.
+pub fn each<C, T, F>(f: F) -> impl Fn(&C, &mut fmt::Formatter) -> fmt::Result
+where
+    C: ?Sized,
+    T: ?Sized,
+    for<'a> &'a C: IntoIterator<Item = &'a T>,
+    F: DebugFormatter<T>,
+{
+    move |v, fmt| {
+        let f = &f;
+
+        fmt.debug_list()
+            .entries(
+                v.into_iter()
+                    .map(|item| debug_fn(move |fmt| DebugFormatter::fmt(f, item, fmt))),
+            )
+            .finish()
+    }
+}
+
+/// Formats the value of an [Option] with `f`, keeping the `Some(..)` around it.
+pub fn some<T, F>(f: F) -> impl Fn(&Option<T>, &mut fmt::Formatter) -> fmt::Result
+where
+    F: DebugFormatter<T>,
+{
+    move |v, fmt| match v {
+        Some(value) => fmt
+            .debug_tuple("Some")
+            .field(&debug_fn(|fmt| DebugFormatter::fmt(&f, value, fmt)))
+            .finish(),
+        None => fmt.write_str("None"),
+    }
+}
+
+/// Formats the value of an [Option] without the `Some(..)` around it,
+/// or `default` if there is none.
+pub fn some_or<T>(default: &str) -> impl Fn(&Option<T>, &mut fmt::Formatter) -> fmt::Result + '_
+where
+    T: fmt::Debug,
+{
+    move |v, fmt| match v {
+        Some(value) => value.fmt(fmt),
+        None => fmt.write_str(default),
+    }
+}
+
+/// Formats the values of a map-like container with `f`.
+pub fn map_values<C, K, V, F>(f: F) -> impl Fn(&C, &mut fmt::Formatter) -> fmt::Result
+where
+    C: ?Sized,
+    K: fmt::Debug + ?Sized,
+    V: ?Sized,
+    for<'a> &'a C: IntoIterator<Item = (&'a K, &'a V)>,
+    F: DebugFormatter<V>,
+{
+    move |v, fmt| {
+        let f = &f;
+
+        fmt.debug_map()
+            .entries(
+                v.into_iter().map(|(key, value)| {
+                    (key, debug_fn(move |fmt| DebugFormatter::fmt(f, value, fmt)))
+                }),
+            )
+            .finish()
+    }
+}
+
+/// Formats the keys of a map-like container with `f`.
+pub fn map_keys<C, K, V, F>(f: F) -> impl Fn(&C, &mut fmt::Formatter) -> fmt::Result
+where
+    C: ?Sized,
+    K: ?Sized,
+    V: fmt::Debug + ?Sized,
+    for<'a> &'a C: IntoIterator<Item = (&'a K, &'a V)>,
+    F: DebugFormatter<K>,
+{
+    move |v, fmt| {
+        let f = &f;
+
+        fmt.debug_map()
+            .entries(
+                v.into_iter().map(|(key, value)| {
+                    (debug_fn(move |fmt| DebugFormatter::fmt(f, key, fmt)), value)
+                }),
+            )
+            .finish()
+    }
+}
+
+/// Formats the value of a [Result] with `f` and the error with `g`,
+/// keeping the `Ok(..)` or `Err(..)` around them.
+pub fn ok_err<T, E, F, G>(f: F, g: G) -> impl Fn(&Result<T, E>, &mut fmt::Formatter) -> fmt::Result
+where
+    F: DebugFormatter<T>,
+    G: DebugFormatter<E>,
+{
+    move |v, fmt| match v {
+        Ok(value) => fmt
+            .debug_tuple("Ok")
+            .field(&debug_fn(|fmt| DebugFormatter::fmt(&f, value, fmt)))
+            .finish(),
+        Err(err) => fmt
+            .debug_tuple("Err")
+            .field(&debug_fn(|fmt| DebugFormatter::fmt(&g, err, fmt)))
+            .finish(),
+    }
+}
@@ -0,0 +1,88 @@
+use core::fmt;
+
+use crate::DebugFormatter;
+
+/// Formats a value using a [DebugFormatter] instead of its own [Debug](fmt::Debug) impl.
+///
+/// This is the adapter used for the `with` attribute, which makes
+/// the same formatters usable in manual `Debug` impls:
+///
+/// ```
+/// use custom_debug::{hexbuf, DebugWith};
+/// use std::fmt;
+///
+/// struct Packet {
+///     payload: Vec<u8>,
+/// }
+///
+/// impl fmt::Debug for Packet {
+///     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+///         f.debug_struct("Packet")
+///             .field("payload", &DebugWith::new(&self.payload, hexbuf))
+///             .finish()
+///     }
+/// }
+/// ```
+pub struct DebugWith<'a, T: ?Sized, F> {
+    data: &'a T,
+    fmt: F,
+}
+
+impl<'a, T: ?Sized, F> DebugWith<'a, T, F>
+where
+    F: DebugFormatter<T>,
+{
+    pub fn new(data: &'a T, fmt: F) -> Self {
+        Self { data, fmt }
+    }
+}
+
+impl<T: ?Sized, F> fmt::Debug for DebugWith<'_, T, F>
+where
+    F: DebugFormatter<T>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        DebugFormatter::fmt(&self.fmt, self.data, f)
+    }
+}
+
+/// Same as the [Debug](fmt::Debug) impl, which allows
+/// using the formatter's output in format strings with `{}`.
+impl<T: ?Sized, F> fmt::Display for DebugWith<'_, T, F>
+where
+    F: DebugFormatter<T>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        DebugFormatter::fmt(&self.fmt, self.data, f)
+    }
+}
+
+/// Implements [Debug](fmt::Debug) using a closure.
+///
+/// Created by [debug_fn].
+pub struct DebugFn<F> {
+    fmt: F,
+}
+
+impl<F> fmt::Debug for DebugFn<F>
+where
+    F: Fn(&mut fmt::Formatter) -> fmt::Result,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        (self.fmt)(f)
+    }
+}
+
+/// Creates a value whose [Debug](fmt::Debug) impl calls `fmt`.
+///
+/// ```
+/// let answer = custom_debug::debug_fn(|f| write!(f, "{:#x}", 42));
+///
+/// assert_eq!(format!("{:?}", answer), "0x2a");
+/// ```
+pub fn debug_fn<F>(fmt: F) -> DebugFn<F>
+where
+    F: Fn(&mut fmt::Formatter) -> fmt::Result,
+{
+    DebugFn { fmt }
+}
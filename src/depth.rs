@@ -0,0 +1,111 @@
+use core::cell::Cell;
+use core::fmt;
+use std::thread::LocalKey;
+
+std::thread_local! {
+    /// Number of derived impls currently being formatted on this thread.
+    static DEPTH: Cell<usize> = const { Cell::new(0) };
+    /// Depth from which derived values are elided.
+    static LIMIT: Cell<usize> = const { Cell::new(usize::MAX) };
+}
+
+/// Limits how deeply nested values with derived impls are formatted.
+///
+/// Values nested deeper than the limit are elided as `{..}`.
+/// Only derived values of types with a `max_depth` container attribute count,
+/// which can be given without a limit of its own.
+///
+/// ```
+/// use custom_debug::{Debug, Limited};
+///
+/// #[derive(Debug)]
+/// #[debug(max_depth)]
+/// struct Tree {
+///     children: Vec<Tree>,
+/// }
+///
+/// let tree = Tree {
+///     children: vec![Tree {
+///         children: vec![Tree { children: vec![] }],
+///     }],
+/// };
+///
+/// # #[cfg(not(feature = "minimal"))]
+/// assert_eq!(
+///     format!("{:?}", Limited::new(&tree).depth(2)),
+///     "Tree { children: [Tree { children: [{..}] }] }",
+/// );
+/// ```
+pub struct Limited<'a, T: ?Sized> {
+    value: &'a T,
+    depth: usize,
+}
+
+impl<'a, T: ?Sized> Limited<'a, T> {
+    /// Formats `value` without a limit, until one is set with [depth](Self::depth).
+    pub fn new(value: &'a T) -> Self {
+        Self {
+            value,
+            depth: usize::MAX,
+        }
+    }
+
+    /// Sets how many levels of derived values are formatted, including `value` itself.
+    pub fn depth(self, depth: usize) -> Self {
+        Self { depth, ..self }
+    }
+}
+
+impl<T: fmt::Debug + ?Sized> fmt::Debug for Limited<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let limit = DEPTH.with(Cell::get).saturating_add(self.depth);
+
+        scoped(&LIMIT, limit.min(LIMIT.with(Cell::get)), || {
+            self.value.fmt(f)
+        })
+    }
+}
+
+pub(crate) fn nested(
+    fmt: &mut fmt::Formatter,
+    max_depth: Option<usize>,
+    body: impl FnOnce(&mut fmt::Formatter) -> fmt::Result,
+) -> fmt::Result {
+    let depth = DEPTH.with(Cell::get);
+    let mut limit = LIMIT.with(Cell::get);
+
+    if let Some(max_depth) = max_depth {
+        limit = limit.min(depth.saturating_add(max_depth));
+    }
+
+    if depth >= limit {
+        return fmt.write_str("{..}");
+    }
+
+    crate::shared::call(|| scoped(&LIMIT, limit, || scoped(&DEPTH, depth + 1, || body(fmt))))
+}
+
+/// Sets `key` to `value` while running `f`, restoring it afterwards, even on panics.
+pub(crate) fn scoped<T: Copy + 'static, R>(
+    key: &'static LocalKey<Cell<T>>,
+    value: T,
+    f: impl FnOnce() -> R,
+) -> R {
+    struct Restore<T: Copy + 'static> {
+        key: &'static LocalKey<Cell<T>>,
+        value: T,
+    }
+
+    impl<T: Copy + 'static> Drop for Restore<T> {
+        fn drop(&mut self) {
+            self.key.with(|cell| cell.set(self.value));
+        }
+    }
+
+    let _restore = Restore {
+        key,
+        value: key.with(|cell| cell.replace(value)),
+    };
+
+    f()
+}
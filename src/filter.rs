@@ -0,0 +1,73 @@
+use core::cell::Cell;
+
+use crate::depth::scoped;
+
+std::thread_local! {
+    /// The innermost filter installed on this thread.
+    static CURRENT: Cell<Option<&'static Frame<'static>>> = const { Cell::new(None) };
+}
+
+struct Frame<'a> {
+    filter: &'a dyn Fn(&str, &str) -> bool,
+    parent: Option<&'a Frame<'a>>,
+}
+
+/// Runs `f` with a filter that decides which named fields derived impls show.
+///
+/// Only types with the `#[debug(filter)]` container attribute are filtered.
+/// The filter is called with the names of the type and the field.
+/// Nested filters only show fields that all of them accept.
+/// Fields of tuple structs and variants have no name and are always shown.
+///
+/// No filters apply to values formatted by a filter itself,
+/// so a filter can format values with derived impls without calling itself again.
+///
+/// ```
+/// use custom_debug::{with_filter, Debug};
+///
+/// #[derive(Debug)]
+/// #[debug(filter)]
+/// struct Account {
+///     id: u32,
+///     email: &'static str,
+/// }
+///
+/// let account = Account { id: 7, email: "ferris@example.com" };
+/// let output = with_filter(|_, field| field != "email", || format!("{:?}", account));
+///
+/// # #[cfg(not(feature = "minimal"))]
+/// assert_eq!(output, "Account { id: 7 }");
+/// ```
+pub fn with_filter<R>(filter: impl Fn(&str, &str) -> bool, f: impl FnOnce() -> R) -> R {
+    let frame = Frame {
+        filter: &filter,
+        parent: CURRENT.with(Cell::get),
+    };
+    // SAFETY: Only `show_field` reads `CURRENT`, without keeping the reference,
+    // and `scoped` restores the parent before `frame` is dropped, even on panics.
+    let frame = unsafe { core::mem::transmute::<&Frame<'_>, &'static Frame<'static>>(&frame) };
+
+    scoped(&CURRENT, Some(frame), f)
+}
+
+/// Whether the installed filters show the field `field_name` of `type_name`.
+#[inline]
+pub(crate) fn show_field(type_name: &str, field_name: &str) -> bool {
+    let Some(frame) = CURRENT.with(Cell::get) else {
+        return true;
+    };
+
+    scoped(&CURRENT, None, || {
+        let mut frame = Some(frame);
+
+        while let Some(current) = frame {
+            if !(current.filter)(type_name, field_name) {
+                return false;
+            }
+
+            frame = current.parent;
+        }
+
+        true
+    })
+}
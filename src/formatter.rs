@@ -0,0 +1,102 @@
+use core::fmt;
+
+use crate::debug_fn;
+
+/// A formatter usable with the `with` attribute.
+///
+/// Plain functions and closures with the signature
+/// `fn(&T, &mut fmt::Formatter) -> fmt::Result` implement this trait.
+/// Types implementing it can carry configuration, which
+/// `#[debug(with(Type, option = value))]` fills in on top of [Default]:
+///
+/// ```
+/// use custom_debug::{Debug, HexBuf};
+///
+/// #[derive(Debug)]
+/// struct Frame {
+///     #[debug(with(HexBuf, max = 32))]
+///     payload: Vec<u8>,
+/// }
+/// ```
+pub trait DebugFormatter<T: ?Sized> {
+    fn fmt(&self, value: &T, f: &mut fmt::Formatter) -> fmt::Result;
+}
+
+impl<T: ?Sized, F> DebugFormatter<T> for F
+where
+    F: Fn(&T, &mut fmt::Formatter) -> fmt::Result,
+{
+    fn fmt(&self, value: &T, f: &mut fmt::Formatter) -> fmt::Result {
+        self(value, f)
+    }
+}
+
+/// Configurable version of [hexbuf](crate::hexbuf) and [hexbuf_str](crate::hexbuf_str).
+#[derive(Clone, Copy, PartialEq, Eq, core::fmt::Debug)]
+pub struct HexBuf {
+    /// Number of bytes to show before truncating.
+    pub max: usize,
+    /// Whether to show printable ascii characters as is, like [hexbuf_str](crate::hexbuf_str).
+    pub ascii: bool,
+}
+
+impl Default for HexBuf {
+    fn default() -> Self {
+        Self {
+            max: usize::MAX,
+            ascii: false,
+        }
+    }
+}
+
+impl<T: AsRef<[u8]> + ?Sized> DebugFormatter<T> for HexBuf {
+    fn fmt(&self, value: &T, f: &mut fmt::Formatter) -> fmt::Result {
+        let buf = value.as_ref();
+        let shown = &buf[..buf.len().min(self.max)];
+
+        if self.ascii {
+            crate::hexbuf_str(&shown, f)?;
+        } else {
+            crate::hexbuf(&shown, f)?;
+        }
+
+        if buf.len() > shown.len() {
+            write!(f, "… ({} bytes)", buf.len())?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Formats the first `max` items of a list-like container, followed by `..` if there are more.
+#[derive(Clone, Copy, PartialEq, Eq, core::fmt::Debug)]
+pub struct Truncate {
+    /// Number of items to show before truncating.
+    pub max: usize,
+}
+
+impl Default for Truncate {
+    fn default() -> Self {
+        Self { max: usize::MAX }
+    }
+}
+
+impl<C, T> DebugFormatter<C> for Truncate
+where
+    C: ?Sized,
+    T: fmt::Debug + ?Sized,
+    for<'a> &'a C: IntoIterator<Item = &'a T>,
+{
+    fn fmt(&self, value: &C, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut items = value.into_iter();
+        let mut list = f.debug_list();
+
+        list.entries(items.by_ref().take(self.max));
+
+        if items.next().is_some() {
+            list.entry(&debug_fn(|f| f.write_str("..")));
+        }
+
+        list.finish()
+    }
+}
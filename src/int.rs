@@ -0,0 +1,104 @@
+use core::fmt;
+
+use crate::hexbuf;
+
+/// Byte order used to decode [IntBytes].
+#[derive(Clone, Copy, PartialEq, Eq, core::fmt::Debug)]
+pub enum Endian {
+    Big,
+    Little,
+}
+
+/// Byte arrays that can be decoded into an integer, and slices, arrays and `Vec`s of those.
+pub trait IntBytes {
+    /// Formats the decoded integer, followed by the raw bytes if `raw` is set.
+    fn fmt_int(&self, endian: Endian, raw: bool, f: &mut fmt::Formatter) -> fmt::Result;
+}
+
+macro_rules! impl_int_bytes {
+    ($($len:literal => $int:ty),*) => {
+        $(
+            impl IntBytes for [u8; $len] {
+                fn fmt_int(&self, endian: Endian, raw: bool, f: &mut fmt::Formatter) -> fmt::Result {
+                    let int = match endian {
+                        Endian::Big => <$int>::from_be_bytes(*self),
+                        Endian::Little => <$int>::from_le_bytes(*self),
+                    };
+
+                    fmt::Debug::fmt(&int, f)?;
+
+                    if raw {
+                        write!(f, " (")?;
+                        hexbuf(self, f)?;
+                        write!(f, ")")?;
+                    }
+
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+
+impl_int_bytes!(2 => u16, 4 => u32, 8 => u64, 16 => u128);
+
+impl<T: IntBytes> IntBytes for [T] {
+    fn fmt_int(&self, endian: Endian, raw: bool, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list()
+            .entries(self.iter().map(|bytes| DecodedInt { bytes, endian, raw }))
+            .finish()
+    }
+}
+
+impl<T: IntBytes, const N: usize> IntBytes for [T; N] {
+    fn fmt_int(&self, endian: Endian, raw: bool, f: &mut fmt::Formatter) -> fmt::Result {
+        self[..].fmt_int(endian, raw, f)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: IntBytes> IntBytes for alloc::vec::Vec<T> {
+    fn fmt_int(&self, endian: Endian, raw: bool, f: &mut fmt::Formatter) -> fmt::Result {
+        self[..].fmt_int(endian, raw, f)
+    }
+}
+
+impl<T: IntBytes + ?Sized> IntBytes for &T {
+    fn fmt_int(&self, endian: Endian, raw: bool, f: &mut fmt::Formatter) -> fmt::Result {
+        (**self).fmt_int(endian, raw, f)
+    }
+}
+
+struct DecodedInt<'a, T: ?Sized> {
+    bytes: &'a T,
+    endian: Endian,
+    raw: bool,
+}
+
+impl<T: IntBytes + ?Sized> fmt::Debug for DecodedInt<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.bytes.fmt_int(self.endian, self.raw, f)
+    }
+}
+
+/// Formats a byte array as the big endian integer it encodes.
+/// Slices of byte arrays are formatted as a list of integers.
+pub fn be(v: &(impl IntBytes + ?Sized), f: &mut fmt::Formatter) -> fmt::Result {
+    v.fmt_int(Endian::Big, false, f)
+}
+
+/// Formats a byte array as the little endian integer it encodes.
+/// Slices of byte arrays are formatted as a list of integers.
+pub fn le(v: &(impl IntBytes + ?Sized), f: &mut fmt::Formatter) -> fmt::Result {
+    v.fmt_int(Endian::Little, false, f)
+}
+
+/// Like [be], but also formats the raw bytes after each integer.
+pub fn be_raw(v: &(impl IntBytes + ?Sized), f: &mut fmt::Formatter) -> fmt::Result {
+    v.fmt_int(Endian::Big, true, f)
+}
+
+/// Like [le], but also formats the raw bytes after each integer.
+pub fn le_raw(v: &(impl IntBytes + ?Sized), f: &mut fmt::Formatter) -> fmt::Result {
+    v.fmt_int(Endian::Little, true, f)
+}
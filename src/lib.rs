@@ -1,9 +1,79 @@
 #![no_std]
 use core::fmt;
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+#[cfg(all(
+    custom_debug_reveal,
+    not(debug_assertions),
+    not(custom_debug_allow_release_reveal)
+))]
+compile_error!(
+    "`--cfg custom_debug_reveal` prints redacted and sensitive fields \
+     and is only allowed in debug builds, unless `--cfg custom_debug_allow_release_reveal` is set"
+);
+
+// Lets derived impls in the tests refer to `::custom_debug`
+#[cfg(test)]
+extern crate self as custom_debug;
+
+pub use capped::Capped;
+pub use combinators::{each, map_keys, map_values, ok_err, some, some_or};
 /// Alias of [Debug]
 pub use custom_debug_derive::Debug as CustomDebug;
 pub use custom_debug_derive::*;
+pub use debug_with::{debug_fn, DebugFn, DebugWith};
+#[cfg(feature = "std")]
+pub use depth::Limited;
+#[cfg(feature = "std")]
+pub use filter::with_filter;
+pub use formatter::{DebugFormatter, HexBuf, Truncate};
+pub use int::{be, be_raw, le, le_raw, Endian, IntBytes};
+pub use net::{ipv4, ipv4_socket, ipv6, ipv6_socket, mac, Ipv4Octets, Ipv6Octets, MacOctets, Port};
+pub use profile::{in_profile, DebugProfile, Profile, Profiled};
+pub use render::{render_into, render_into_pretty, ArrayString, Rendered};
+#[cfg(feature = "std")]
+pub use shared::{shared, SharedPtr};
+pub use sorted::{sorted, MapLike, SetLike, SortedCollection};
+#[cfg(feature = "alloc")]
+pub use sorted::{sorted_by_debug, SortedByDebugCollection};
+pub use timestamp::{
+    timestamp_micros, timestamp_millis, timestamp_nanos, timestamp_secs, TimeUnit, UtcDateTime,
+};
+#[cfg(feature = "std")]
+pub use verbose::{with_verbosity, Verbose};
+#[cfg(feature = "serde")]
+pub use view::{DebugView, SerializeDebug};
+pub use visit::{Visit, Visitor};
+
+#[doc(hidden)]
+pub mod __private;
+mod capped;
+mod combinators;
+mod debug_with;
+#[cfg(feature = "std")]
+mod depth;
+#[cfg(feature = "std")]
+mod filter;
+mod formatter;
+mod int;
+mod net;
+mod profile;
+mod render;
+#[cfg(feature = "std")]
+mod shared;
+mod sorted;
+#[cfg(test)]
+mod tests;
+mod timestamp;
+#[cfg(feature = "std")]
+mod verbose;
+#[cfg(feature = "serde")]
+mod view;
+mod visit;
 
 /// Formats a buffer as hex using \xNN notation.
 pub fn hexbuf(v: &impl AsRef<[u8]>, f: &mut fmt::Formatter) -> fmt::Result {
@@ -36,3 +106,140 @@ pub fn hexbuf_str(v: &impl AsRef<[u8]>, f: &mut fmt::Formatter) -> fmt::Result {
 
     Ok(())
 }
+
+/// Formats a value using its [Display](fmt::Display) impl.
+pub fn display(v: &impl fmt::Display, f: &mut fmt::Formatter) -> fmt::Result {
+    fmt::Display::fmt(v, f)
+}
+
+/// Formats an integer as hex with a `0x` prefix.
+pub fn hex(v: &impl fmt::LowerHex, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{:#x}", v)
+}
+
+/// Formats a buffer as standard base64 (RFC 4648, with padding).
+pub fn base64(v: &impl AsRef<[u8]>, f: &mut fmt::Formatter) -> fmt::Result {
+    write_base64(v.as_ref(), usize::MAX, BASE64_STANDARD, f)
+}
+
+/// Formats a buffer as URL-safe base64 (RFC 4648, without padding).
+pub fn base64url(v: &impl AsRef<[u8]>, f: &mut fmt::Formatter) -> fmt::Result {
+    write_base64(v.as_ref(), usize::MAX, BASE64_URL_SAFE, f)
+}
+
+/// Like [base64], but only encodes the first `MAX` bytes,
+/// followed by the total length if the buffer is longer.
+///
+/// Truncated output is never padded.
+pub fn base64_truncated<const MAX: usize>(
+    v: &impl AsRef<[u8]>,
+    f: &mut fmt::Formatter,
+) -> fmt::Result {
+    write_base64(v.as_ref(), MAX, BASE64_STANDARD, f)
+}
+
+/// Like [base64url], but only encodes the first `MAX` bytes,
+/// followed by the total length if the buffer is longer.
+pub fn base64url_truncated<const MAX: usize>(
+    v: &impl AsRef<[u8]>,
+    f: &mut fmt::Formatter,
+) -> fmt::Result {
+    write_base64(v.as_ref(), MAX, BASE64_URL_SAFE, f)
+}
+
+/// Alphabets and whether to pad the output
+const BASE64_STANDARD: (&[u8; 64], bool) = (
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/",
+    true,
+);
+const BASE64_URL_SAFE: (&[u8; 64], bool) = (
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_",
+    false,
+);
+
+fn write_base64(
+    buf: &[u8],
+    max: usize,
+    (alphabet, pad): (&[u8; 64], bool),
+    f: &mut fmt::Formatter,
+) -> fmt::Result {
+    let truncated = buf.len() > max;
+
+    for chunk in buf[..buf.len().min(max)].chunks(3) {
+        let mut bytes = [0; 3];
+        bytes[..chunk.len()].copy_from_slice(chunk);
+        let bits = u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]);
+
+        let mut encoded = [b'='; 4];
+        let len = chunk.len() + 1;
+
+        for (i, c) in encoded[..len].iter_mut().enumerate() {
+            *c = alphabet[(bits >> (18 - 6 * i) & 0x3f) as usize];
+        }
+
+        // Padding in front of the length would read like the end of the data
+        let encoded = if pad && !truncated {
+            &encoded[..]
+        } else {
+            &encoded[..len]
+        };
+
+        for &c in encoded {
+            fmt::Write::write_char(f, c as char)?;
+        }
+    }
+
+    if truncated {
+        write!(f, "… ({} bytes)", buf.len())?;
+    }
+
+    Ok(())
+}
+
+/// Values that hold the 16 bytes of a UUID.
+///
+/// Integers are taken to be the UUID's numeric value, i.e. big endian.
+pub trait UuidBytes {
+    fn uuid_bytes(&self) -> [u8; 16];
+}
+
+impl UuidBytes for [u8; 16] {
+    fn uuid_bytes(&self) -> [u8; 16] {
+        *self
+    }
+}
+
+impl UuidBytes for u128 {
+    fn uuid_bytes(&self) -> [u8; 16] {
+        self.to_be_bytes()
+    }
+}
+
+/// Formats 16 bytes as a UUID (`xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx`).
+pub fn uuid(v: &impl UuidBytes, f: &mut fmt::Formatter) -> fmt::Result {
+    write_uuid(&v.uuid_bytes(), f)
+}
+
+/// Formats 16 bytes as a Microsoft GUID,
+/// whose first three groups are stored little endian.
+pub fn guid(v: &impl UuidBytes, f: &mut fmt::Formatter) -> fmt::Result {
+    let mut bytes = v.uuid_bytes();
+
+    bytes[0..4].reverse();
+    bytes[4..6].reverse();
+    bytes[6..8].reverse();
+
+    write_uuid(&bytes, f)
+}
+
+fn write_uuid(bytes: &[u8; 16], f: &mut fmt::Formatter) -> fmt::Result {
+    for (i, x) in bytes.iter().enumerate() {
+        if let 4 | 6 | 8 | 10 = i {
+            write!(f, "-")?;
+        }
+
+        write!(f, "{:02x}", x)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,162 @@
+use core::fmt;
+
+/// Values that hold an IPv4 address.
+///
+/// Integers are taken to be in network byte order, as in `in_addr.s_addr`.
+pub trait Ipv4Octets {
+    fn ipv4_octets(&self) -> [u8; 4];
+}
+
+impl Ipv4Octets for [u8; 4] {
+    fn ipv4_octets(&self) -> [u8; 4] {
+        *self
+    }
+}
+
+impl Ipv4Octets for u32 {
+    fn ipv4_octets(&self) -> [u8; 4] {
+        self.to_ne_bytes()
+    }
+}
+
+/// Values that hold an IPv6 address.
+///
+/// Integers are taken to be in network byte order, as in `in6_addr`.
+pub trait Ipv6Octets {
+    fn ipv6_octets(&self) -> [u8; 16];
+}
+
+impl Ipv6Octets for [u8; 16] {
+    fn ipv6_octets(&self) -> [u8; 16] {
+        *self
+    }
+}
+
+impl Ipv6Octets for u128 {
+    fn ipv6_octets(&self) -> [u8; 16] {
+        self.to_ne_bytes()
+    }
+}
+
+/// Values that hold a MAC (EUI-48) address.
+pub trait MacOctets {
+    fn mac_octets(&self) -> [u8; 6];
+}
+
+impl MacOctets for [u8; 6] {
+    fn mac_octets(&self) -> [u8; 6] {
+        *self
+    }
+}
+
+/// Values that hold a port number.
+///
+/// Like addresses, integers are taken to be in network byte order, as in `sin_port`.
+pub trait Port {
+    fn port(&self) -> u16;
+}
+
+impl Port for [u8; 2] {
+    fn port(&self) -> u16 {
+        u16::from_be_bytes(*self)
+    }
+}
+
+impl Port for u16 {
+    fn port(&self) -> u16 {
+        u16::from_be(*self)
+    }
+}
+
+/// Formats an IPv4 address in dotted decimal notation.
+pub fn ipv4(v: &impl Ipv4Octets, f: &mut fmt::Formatter) -> fmt::Result {
+    let [a, b, c, d] = v.ipv4_octets();
+
+    write!(f, "{}.{}.{}.{}", a, b, c, d)
+}
+
+/// Formats an IPv6 address in the compressed form of RFC 5952.
+pub fn ipv6(v: &impl Ipv6Octets, f: &mut fmt::Formatter) -> fmt::Result {
+    let octets = v.ipv6_octets();
+    let mut groups = [0u16; 8];
+
+    for (group, pair) in groups.iter_mut().zip(octets.chunks_exact(2)) {
+        *group = u16::from_be_bytes([pair[0], pair[1]]);
+    }
+
+    if groups[..6] == [0, 0, 0, 0, 0, 0xffff] {
+        let [a, b, c, d] = [octets[12], octets[13], octets[14], octets[15]];
+
+        return write!(f, "::ffff:{}.{}.{}.{}", a, b, c, d);
+    }
+
+    let (zeros_start, zeros_len) = longest_zero_run(&groups);
+
+    if zeros_len < 2 {
+        return write_groups(&groups, f);
+    }
+
+    write_groups(&groups[..zeros_start], f)?;
+    write!(f, "::")?;
+    write_groups(&groups[zeros_start + zeros_len..], f)
+}
+
+/// Formats a MAC address as colon-separated lowercase hex.
+pub fn mac(v: &impl MacOctets, f: &mut fmt::Formatter) -> fmt::Result {
+    let [a, b, c, d, e, g] = v.mac_octets();
+
+    write!(
+        f,
+        "{:02x}:{:02x}:{:02x}:{:02x}:{:02x}:{:02x}",
+        a, b, c, d, e, g
+    )
+}
+
+/// Formats an `(address, port)` pair as `a.b.c.d:port`.
+pub fn ipv4_socket<A: Ipv4Octets, P: Port>(v: &(A, P), f: &mut fmt::Formatter) -> fmt::Result {
+    ipv4(&v.0, f)?;
+
+    write!(f, ":{}", v.1.port())
+}
+
+/// Formats an `(address, port)` pair as `[address]:port`.
+pub fn ipv6_socket<A: Ipv6Octets, P: Port>(v: &(A, P), f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "[")?;
+    ipv6(&v.0, f)?;
+
+    write!(f, "]:{}", v.1.port())
+}
+
+/// Finds the first longest run of zero groups, as `(start, len)`.
+fn longest_zero_run(groups: &[u16; 8]) -> (usize, usize) {
+    let mut longest = (0, 0);
+    let mut i = 0;
+
+    while i < groups.len() {
+        let start = i;
+
+        while i < groups.len() && groups[i] == 0 {
+            i += 1;
+        }
+
+        if i - start > longest.1 {
+            longest = (start, i - start);
+        }
+
+        i += 1;
+    }
+
+    longest
+}
+
+fn write_groups(groups: &[u16], f: &mut fmt::Formatter) -> fmt::Result {
+    for (i, group) in groups.iter().enumerate() {
+        if i > 0 {
+            write!(f, ":")?;
+        }
+
+        write!(f, "{:x}", group)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,74 @@
+use core::fmt;
+use core::marker::PhantomData;
+
+/// Alternative [Debug](fmt::Debug) output of a type for the profile `P`.
+///
+/// Derived for every profile named in a field attribute, e.g. `#[debug(profile(log), skip)]`,
+/// which is scoped to the profile type `Log`.
+pub trait DebugProfile<P: ?Sized> {
+    fn fmt_profile(&self, f: &mut fmt::Formatter) -> fmt::Result;
+}
+
+/// Selects the profile `P` for formatting a value.
+///
+/// ```
+/// use custom_debug::{Debug, Profile};
+///
+/// struct Log;
+///
+/// #[derive(Debug)]
+/// struct Login {
+///     user: String,
+///     #[debug(profile(log), skip)]
+///     password: String,
+/// }
+///
+/// let login = Login {
+///     user: "ferris".into(),
+///     password: "hunter2".into(),
+/// };
+///
+/// # #[cfg(not(feature = "minimal"))]
+/// assert_eq!(
+///     format!("{:?}", Profile::<Log>::of(&login)),
+///     r#"Login { user: "ferris" }"#,
+/// );
+/// ```
+pub struct Profile<P: ?Sized> {
+    _profile: PhantomData<P>,
+}
+
+impl<P: ?Sized> Profile<P> {
+    /// Formats `value` using its profile `P`.
+    pub fn of<T>(value: &T) -> Profiled<'_, P, T>
+    where
+        T: DebugProfile<P> + ?Sized,
+    {
+        Profiled {
+            value,
+            _profile: PhantomData,
+        }
+    }
+}
+
+/// Formats a value using its profile `P`.
+///
+/// Created by [Profile::of].
+pub struct Profiled<'a, P: ?Sized, T: ?Sized> {
+    value: &'a T,
+    _profile: PhantomData<P>,
+}
+
+impl<P: ?Sized, T> fmt::Debug for Profiled<'_, P, T>
+where
+    T: DebugProfile<P> + ?Sized,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.value.fmt_profile(f)
+    }
+}
+
+/// Formats a field using its profile `P`, e.g. `with = in_profile::<Log>`.
+pub fn in_profile<P: ?Sized>(v: &impl DebugProfile<P>, f: &mut fmt::Formatter) -> fmt::Result {
+    v.fmt_profile(f)
+}
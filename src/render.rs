@@ -0,0 +1,200 @@
+use core::fmt::{self, Write};
+use core::ops::Deref;
+
+/// Formats `value` into `buf`, without allocating.
+///
+/// Output that doesn't fit is dropped at a character boundary,
+/// and the number of dropped bytes is reported in [Rendered::dropped].
+///
+/// ```
+/// let mut buf = [0; 8];
+/// let rendered = custom_debug::render_into(&[1, 2, 3, 4], &mut buf);
+///
+/// assert_eq!(rendered.text, "[1, 2, 3");
+/// assert_eq!(rendered.dropped, 4);
+/// ```
+pub fn render_into<'a, T>(value: &T, buf: &'a mut [u8]) -> Rendered<'a>
+where
+    T: fmt::Debug + ?Sized,
+{
+    render_args(format_args!("{:?}", value), buf)
+}
+
+/// Like [render_into], but pretty-prints `value` like `{:#?}`.
+pub fn render_into_pretty<'a, T>(value: &T, buf: &'a mut [u8]) -> Rendered<'a>
+where
+    T: fmt::Debug + ?Sized,
+{
+    render_args(format_args!("{:#?}", value), buf)
+}
+
+fn render_args<'a>(args: fmt::Arguments, buf: &'a mut [u8]) -> Rendered<'a> {
+    let mut writer = TruncatingWriter {
+        buf,
+        len: 0,
+        dropped: 0,
+    };
+
+    // The writer never fails, so errors come from the value's impl.
+    // What has been written up to that point is kept.
+    let _ = writer.write_fmt(args);
+
+    let TruncatingWriter { buf, len, dropped } = writer;
+
+    Rendered {
+        text: buf_str(&buf[..len]),
+        dropped,
+    }
+}
+
+/// Output of [render_into].
+#[derive(Clone, Copy, PartialEq, Eq, core::fmt::Debug)]
+pub struct Rendered<'a> {
+    /// The output that fit into the buffer.
+    pub text: &'a str,
+    /// The number of bytes that didn't fit.
+    pub dropped: usize,
+}
+
+impl Rendered<'_> {
+    /// Whether any output was dropped.
+    pub fn is_truncated(&self) -> bool {
+        self.dropped > 0
+    }
+}
+
+impl Deref for Rendered<'_> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.text
+    }
+}
+
+impl fmt::Display for Rendered<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.text)
+    }
+}
+
+/// A string of at most `N` bytes stored inline, for formatting without allocating.
+///
+/// Writes that don't fit are dropped at a character boundary
+/// and counted in [dropped](Self::dropped) instead of failing.
+///
+/// ```
+/// use core::fmt::Write;
+/// use custom_debug::ArrayString;
+///
+/// let mut s = ArrayString::<16>::new();
+/// write!(s, "{:?}", Some("value")).unwrap();
+///
+/// assert_eq!(s.as_str(), "Some(\"value\")");
+/// ```
+#[derive(Clone)]
+pub struct ArrayString<const N: usize> {
+    buf: [u8; N],
+    len: usize,
+    dropped: usize,
+}
+
+impl<const N: usize> ArrayString<N> {
+    pub const fn new() -> Self {
+        Self {
+            buf: [0; N],
+            len: 0,
+            dropped: 0,
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        buf_str(&self.buf[..self.len])
+    }
+
+    /// The number of bytes that didn't fit.
+    pub fn dropped(&self) -> usize {
+        self.dropped
+    }
+
+    pub fn clear(&mut self) {
+        self.len = 0;
+        self.dropped = 0;
+    }
+}
+
+impl<const N: usize> Default for ArrayString<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Write for ArrayString<N> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut writer = TruncatingWriter {
+            buf: &mut self.buf,
+            len: self.len,
+            dropped: self.dropped,
+        };
+
+        writer.write_str(s)?;
+
+        self.len = writer.len;
+        self.dropped = writer.dropped;
+
+        Ok(())
+    }
+}
+
+impl<const N: usize> Deref for ArrayString<N> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const N: usize> fmt::Display for ArrayString<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl<const N: usize> fmt::Debug for ArrayString<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+/// Writes into a buffer until the first string that doesn't fit,
+/// counting the bytes of that and any later strings.
+struct TruncatingWriter<'a> {
+    buf: &'a mut [u8],
+    len: usize,
+    dropped: usize,
+}
+
+impl Write for TruncatingWriter<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if self.dropped > 0 {
+            self.dropped += s.len();
+            return Ok(());
+        }
+
+        let mut len = s.len().min(self.buf.len() - self.len);
+
+        while !s.is_char_boundary(len) {
+            len -= 1;
+        }
+
+        self.buf[self.len..][..len].copy_from_slice(&s.as_bytes()[..len]);
+        self.len += len;
+        self.dropped = s.len() - len;
+
+        Ok(())
+    }
+}
+
+/// Only whole strings are written, so the buffer is always valid UTF-8.
+fn buf_str(buf: &[u8]) -> &str {
+    core::str::from_utf8(buf).unwrap_or_default()
+}
@@ -0,0 +1,96 @@
+use alloc::collections::BTreeMap;
+use alloc::rc::Rc;
+use alloc::sync::Arc;
+use core::cell::{Cell, RefCell};
+use core::fmt;
+
+use crate::depth::scoped;
+
+std::thread_local! {
+    /// Addresses of the shared values formatted during the current format call.
+    static VISITED: RefCell<BTreeMap<usize, Visit>> = const { RefCell::new(BTreeMap::new()) };
+    /// Number of derived impls and [shared] formatters currently running on this thread.
+    static ACTIVE: Cell<usize> = const { Cell::new(0) };
+}
+
+#[derive(Clone, Copy)]
+struct Visit {
+    id: usize,
+    done: bool,
+}
+
+/// Pointers to values that may be shared, or even form cycles.
+pub trait SharedPtr {
+    type Target: fmt::Debug + ?Sized;
+
+    fn shared_target(&self) -> &Self::Target;
+}
+
+impl<T: fmt::Debug + ?Sized> SharedPtr for Rc<T> {
+    type Target = T;
+
+    fn shared_target(&self) -> &T {
+        self
+    }
+}
+
+impl<T: fmt::Debug + ?Sized> SharedPtr for Arc<T> {
+    type Target = T;
+
+    fn shared_target(&self) -> &T {
+        self
+    }
+}
+
+/// Formats the target of an `Rc` or `Arc` once per format call, labeled `#n`.
+///
+/// Later occurrences of the same pointer are printed as `<seen #n>`,
+/// or as `<cycle #n>` while it is still being formatted.
+pub fn shared<P: SharedPtr + ?Sized>(v: &P, f: &mut fmt::Formatter) -> fmt::Result {
+    call(|| {
+        let target = v.shared_target();
+        let addr = target as *const P::Target as *const () as usize;
+
+        let id = VISITED.with(|visited| {
+            let mut visited = visited.borrow_mut();
+            let id = visited.len() + 1;
+
+            match visited.get(&addr) {
+                Some(visit) => Err(*visit),
+                None => {
+                    visited.insert(addr, Visit { id, done: false });
+                    Ok(id)
+                }
+            }
+        });
+
+        let id = match id {
+            Ok(id) => id,
+            Err(Visit { id, done: true }) => return write!(f, "<seen #{}>", id),
+            Err(Visit { id, done: false }) => return write!(f, "<cycle #{}>", id),
+        };
+
+        write!(f, "#{} ", id)?;
+        let result = fmt::Debug::fmt(target, f);
+
+        VISITED.with(|visited| {
+            if let Some(visit) = visited.borrow_mut().get_mut(&addr) {
+                visit.done = true;
+            }
+        });
+
+        result
+    })
+}
+
+/// Runs `f` as part of the current format call,
+/// starting a new one with no shared values seen if there is none.
+pub(crate) fn call<R>(f: impl FnOnce() -> R) -> R {
+    let active = ACTIVE.with(Cell::get);
+
+    if active == 0 {
+        VISITED.with(|visited| visited.borrow_mut().clear());
+    }
+
+    scoped(&ACTIVE, active + 1, f)
+}
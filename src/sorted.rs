@@ -0,0 +1,231 @@
+use core::cmp::Ordering;
+use core::fmt;
+use core::marker::PhantomData;
+
+/// Marker for collections whose references iterate over `&T`, like `HashSet<T>`.
+pub struct SetLike<T: ?Sized>(PhantomData<fn(&T)>);
+
+/// Marker for collections whose references iterate over `(&K, &V)`, like `HashMap<K, V>`.
+pub struct MapLike<K: ?Sized, V: ?Sized>(PhantomData<fn(&K, &V)>);
+
+/// A map or set that [sorted] can format in key order.
+///
+/// `M` is either [SetLike] or [MapLike] and is inferred from the collection.
+pub trait SortedCollection<M> {
+    fn fmt_sorted(&self, f: &mut fmt::Formatter) -> fmt::Result;
+}
+
+impl<C, T> SortedCollection<SetLike<T>> for C
+where
+    C: ?Sized,
+    T: Ord + fmt::Debug,
+    for<'a> &'a C: IntoIterator<Item = &'a T>,
+{
+    fn fmt_sorted(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_sorted(|| self.into_iter(), f)
+    }
+}
+
+impl<C, K, V> SortedCollection<MapLike<K, V>> for C
+where
+    C: ?Sized,
+    K: Ord + fmt::Debug,
+    V: fmt::Debug + ?Sized,
+    for<'a> &'a C: IntoIterator<Item = (&'a K, &'a V)>,
+{
+    fn fmt_sorted(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_sorted(|| self.into_iter(), f)
+    }
+}
+
+/// A map or set that [sorted_by_debug] can format ordered by the `Debug` output of its keys.
+#[cfg(feature = "alloc")]
+pub trait SortedByDebugCollection<M> {
+    fn fmt_sorted_by_debug(&self, f: &mut fmt::Formatter) -> fmt::Result;
+}
+
+#[cfg(feature = "alloc")]
+impl<C, T> SortedByDebugCollection<SetLike<T>> for C
+where
+    C: ?Sized,
+    T: fmt::Debug,
+    for<'a> &'a C: IntoIterator<Item = &'a T>,
+{
+    fn fmt_sorted_by_debug(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_sorted_by_debug(self.into_iter(), f)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<C, K, V> SortedByDebugCollection<MapLike<K, V>> for C
+where
+    C: ?Sized,
+    K: fmt::Debug,
+    V: fmt::Debug + ?Sized,
+    for<'a> &'a C: IntoIterator<Item = (&'a K, &'a V)>,
+{
+    fn fmt_sorted_by_debug(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_sorted_by_debug(self.into_iter(), f)
+    }
+}
+
+/// Formats a map or set (e.g. a `HashMap` or `HashSet`) sorted by key.
+///
+/// Keys are expected to be unique, as they are in maps and sets.
+pub fn sorted<C, M>(v: &C, f: &mut fmt::Formatter) -> fmt::Result
+where
+    C: SortedCollection<M> + ?Sized,
+{
+    v.fmt_sorted(f)
+}
+
+/// Formats a map or set sorted by the [Debug](fmt::Debug) output of its keys,
+/// for keys that don't implement [Ord].
+#[cfg(feature = "alloc")]
+pub fn sorted_by_debug<C, M>(v: &C, f: &mut fmt::Formatter) -> fmt::Result
+where
+    C: SortedByDebugCollection<M> + ?Sized,
+{
+    v.fmt_sorted_by_debug(f)
+}
+
+/// An entry of a map (`(&K, &V)`) or set (`&T`).
+trait Entry<'a>: Copy {
+    #[cfg(feature = "alloc")]
+    fn key(self) -> &'a dyn fmt::Debug;
+
+    fn fmt_entries(entries: impl Iterator<Item = Self>, f: &mut fmt::Formatter) -> fmt::Result;
+}
+
+/// An [Entry] whose key implements [Ord].
+trait OrdEntry<'a>: Entry<'a> {
+    fn cmp_keys(self, other: Self) -> Ordering;
+}
+
+impl<'a, T: fmt::Debug> Entry<'a> for &'a T {
+    #[cfg(feature = "alloc")]
+    fn key(self) -> &'a dyn fmt::Debug {
+        self
+    }
+
+    fn fmt_entries(entries: impl Iterator<Item = Self>, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(entries).finish()
+    }
+}
+
+impl<'a, T: Ord + fmt::Debug> OrdEntry<'a> for &'a T {
+    fn cmp_keys(self, other: Self) -> Ordering {
+        self.cmp(other)
+    }
+}
+
+impl<'a, K, V> Entry<'a> for (&'a K, &'a V)
+where
+    K: fmt::Debug,
+    V: fmt::Debug + ?Sized,
+{
+    #[cfg(feature = "alloc")]
+    fn key(self) -> &'a dyn fmt::Debug {
+        self.0
+    }
+
+    fn fmt_entries(entries: impl Iterator<Item = Self>, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(entries).finish()
+    }
+}
+
+impl<'a, K, V> OrdEntry<'a> for (&'a K, &'a V)
+where
+    K: Ord + fmt::Debug,
+    V: fmt::Debug + ?Sized,
+{
+    fn cmp_keys(self, other: Self) -> Ordering {
+        self.0.cmp(other.0)
+    }
+}
+
+/// Number of entries [sorted] sorts on the stack without the `alloc` feature.
+/// Larger collections are sorted by repeatedly searching for the next smallest key.
+#[cfg(not(feature = "alloc"))]
+const STACK_CAPACITY: usize = 32;
+
+#[cfg(feature = "alloc")]
+fn fmt_sorted<'a, E, I>(entries: impl Fn() -> I, f: &mut fmt::Formatter) -> fmt::Result
+where
+    E: OrdEntry<'a>,
+    I: Iterator<Item = E>,
+{
+    let mut entries = entries().collect::<alloc::vec::Vec<_>>();
+    entries.sort_by(|a, b| a.cmp_keys(*b));
+
+    Entry::fmt_entries(entries.into_iter(), f)
+}
+
+#[cfg(not(feature = "alloc"))]
+fn fmt_sorted<'a, E, I>(entries: impl Fn() -> I, f: &mut fmt::Formatter) -> fmt::Result
+where
+    E: OrdEntry<'a>,
+    I: Iterator<Item = E>,
+{
+    let mut iter = entries();
+    let Some(first) = iter.next() else {
+        return Entry::fmt_entries(iter, f);
+    };
+
+    let mut buf = [first; STACK_CAPACITY];
+    let mut len = 1;
+
+    for entry in iter {
+        if len == STACK_CAPACITY {
+            return fmt_by_selection(entries, f);
+        }
+
+        buf[len] = entry;
+        len += 1;
+    }
+
+    buf[..len].sort_unstable_by(|a, b| a.cmp_keys(*b));
+
+    Entry::fmt_entries(buf[..len].iter().copied(), f)
+}
+
+#[cfg(not(feature = "alloc"))]
+fn fmt_by_selection<'a, E, I>(entries: impl Fn() -> I, f: &mut fmt::Formatter) -> fmt::Result
+where
+    E: OrdEntry<'a>,
+    I: Iterator<Item = E>,
+{
+    let mut previous: Option<E> = None;
+
+    let sorted = core::iter::from_fn(|| {
+        previous = entries()
+            .filter(|entry| match previous {
+                Some(previous) => entry.cmp_keys(previous).is_gt(),
+                None => true,
+            })
+            .min_by(|a, b| a.cmp_keys(*b));
+
+        previous
+    });
+
+    Entry::fmt_entries(sorted, f)
+}
+
+#[cfg(feature = "alloc")]
+fn fmt_sorted_by_debug<'a, E>(
+    entries: impl Iterator<Item = E>,
+    f: &mut fmt::Formatter,
+) -> fmt::Result
+where
+    E: Entry<'a>,
+{
+    use alloc::format;
+    use alloc::vec::Vec;
+
+    let mut entries = entries
+        .map(|entry| (format!("{:?}", entry.key()), entry))
+        .collect::<Vec<_>>();
+    entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    Entry::fmt_entries(entries.into_iter().map(|(_, entry)| entry), f)
+}
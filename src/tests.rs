@@ -0,0 +1,1130 @@
+extern crate std;
+
+use std::format;
+
+use super::*;
+
+#[test]
+fn test_utc_date_time() {
+    let date_time = UtcDateTime::from_unix(1_792_324_800, 0);
+
+    assert_eq!(format!("{}", date_time), "2026-10-18T12:00:00Z");
+    assert_eq!(
+        format!("{}", UtcDateTime::from_unix(951_782_400, 0)),
+        "2000-02-29T00:00:00Z"
+    );
+    assert_eq!(
+        format!("{}", UtcDateTime::from_unix(0, 5_000_000)),
+        "1970-01-01T00:00:00.005000000Z"
+    );
+}
+
+#[test]
+fn test_timestamp_units() {
+    struct Millis(u64);
+
+    impl fmt::Debug for Millis {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            timestamp_millis(&self.0, f)
+        }
+    }
+
+    assert_eq!(
+        format!("{:?}", Millis(1_792_324_800_123)),
+        "1792324800123 (2026-10-18T12:00:00.123Z)"
+    );
+}
+
+#[test]
+fn test_ipv6_compression() {
+    struct Ipv6([u8; 16]);
+
+    impl fmt::Debug for Ipv6 {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            ipv6(&self.0, f)
+        }
+    }
+
+    let ipv6_str = |groups: [u16; 8]| {
+        let mut octets = [0; 16];
+
+        for (pair, group) in octets.chunks_exact_mut(2).zip(groups) {
+            pair.copy_from_slice(&group.to_be_bytes());
+        }
+
+        format!("{:?}", Ipv6(octets))
+    };
+
+    assert_eq!(ipv6_str([0; 8]), "::");
+    assert_eq!(ipv6_str([0, 0, 0, 0, 0, 0, 0, 1]), "::1");
+    assert_eq!(
+        ipv6_str([0x2001, 0xdb8, 0, 0, 1, 0, 0, 1]),
+        "2001:db8::1:0:0:1"
+    );
+    assert_eq!(
+        ipv6_str([0x2001, 0xdb8, 0, 1, 1, 1, 1, 1]),
+        "2001:db8:0:1:1:1:1:1"
+    );
+    assert_eq!(
+        ipv6_str([0, 0, 0, 0, 0, 0xffff, 0xc000, 0x0201]),
+        "::ffff:192.0.2.1"
+    );
+}
+
+#[test]
+fn test_socket_and_mac() {
+    struct Packet {
+        src: (u32, u16),
+        hw: [u8; 6],
+    }
+
+    impl fmt::Debug for Packet {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            ipv4_socket(&self.src, f)?;
+            write!(f, " ")?;
+            mac(&self.hw, f)
+        }
+    }
+
+    let packet = Packet {
+        src: (
+            u32::from_ne_bytes([192, 168, 0, 1]),
+            u16::from_ne_bytes(8080u16.to_be_bytes()),
+        ),
+        hw: [0x00, 0x1b, 0x44, 0x11, 0x3a, 0xb7],
+    };
+
+    assert_eq!(
+        format!("{:?}", packet),
+        "192.168.0.1:8080 00:1b:44:11:3a:b7"
+    );
+}
+
+#[test]
+fn test_uuid_and_guid() {
+    struct Ids {
+        uuid: u128,
+        guid: [u8; 16],
+    }
+
+    impl fmt::Debug for Ids {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            uuid(&self.uuid, f)?;
+            write!(f, " ")?;
+            guid(&self.guid, f)
+        }
+    }
+
+    let ids = Ids {
+        uuid: 0x67e55044_10b1_426f_9247_bb680e5fe0c8,
+        guid: [
+            0x44, 0x50, 0xe5, 0x67, 0xb1, 0x10, 0x6f, 0x42, 0x92, 0x47, 0xbb, 0x68, 0x0e, 0x5f,
+            0xe0, 0xc8,
+        ],
+    };
+
+    assert_eq!(
+        format!("{:?}", ids),
+        "67e55044-10b1-426f-9247-bb680e5fe0c8 67e55044-10b1-426f-9247-bb680e5fe0c8"
+    );
+}
+
+#[test]
+fn test_int_bytes() {
+    struct Wire<'a> {
+        len: [u8; 2],
+        records: &'a [[u8; 4]],
+    }
+
+    impl fmt::Debug for Wire<'_> {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            be_raw(&self.len, f)?;
+            write!(f, " ")?;
+            le(&self.records, f)
+        }
+    }
+
+    let wire = Wire {
+        len: [0x01, 0x02],
+        records: &[[1, 0, 0, 0], [0, 1, 0, 0]],
+    };
+
+    assert_eq!(format!("{:?}", wire), "258 (b\"\\x01\\x02\") [1, 256]");
+
+    #[cfg(all(feature = "alloc", not(feature = "minimal")))]
+    {
+        #[derive(crate::Debug)]
+        struct Frames {
+            #[debug(be)]
+            lens: std::vec::Vec<[u8; 2]>,
+        }
+
+        let frames = Frames {
+            lens: std::vec![[0, 1], [1, 0]],
+        };
+
+        assert_eq!(format!("{:?}", frames), "Frames { lens: [1, 256] }");
+    }
+}
+
+#[test]
+fn test_base64() {
+    struct Key(&'static [u8]);
+
+    impl fmt::Debug for Key {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            base64(&self.0, f)?;
+            write!(f, " ")?;
+            base64url(&self.0, f)?;
+            write!(f, " ")?;
+            base64_truncated::<3>(&self.0, f)
+        }
+    }
+
+    assert_eq!(format!("{:?}", Key(b"")), "  ");
+    assert_eq!(
+        format!("{:?}", Key(b"\xfb\xffab")),
+        "+/9hYg== -_9hYg +/9h… (4 bytes)"
+    );
+    assert_eq!(format!("{:?}", Key(b"\xfb\xff")), "+/8= -_8 +/8=");
+
+    struct Truncated(&'static [u8]);
+
+    impl fmt::Debug for Truncated {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            base64_truncated::<4>(&self.0, f)
+        }
+    }
+
+    assert_eq!(format!("{:?}", Truncated(b"\xfb\xffab")), "+/9hYg==");
+    assert_eq!(
+        format!("{:?}", Truncated(b"\xfb\xffabc")),
+        "+/9hYg… (5 bytes)"
+    );
+}
+
+#[cfg(not(feature = "minimal"))]
+#[test]
+fn test_combinators() {
+    #[derive(crate::Debug)]
+    struct Files {
+        #[debug(with = each(hexbuf))]
+        chunks: std::vec::Vec<std::vec::Vec<u8>>,
+        #[debug(with = some_or("-"))]
+        owner: Option<u32>,
+        #[debug(with = map_values(hex))]
+        modes: std::collections::BTreeMap<&'static str, u32>,
+        #[debug(with = ok_err(display, some(hex)))]
+        status: Result<&'static str, Option<u8>>,
+    }
+
+    let files = Files {
+        chunks: std::vec![std::vec![0, 1], std::vec![]],
+        owner: None,
+        modes: [("a", 0o755)].into_iter().collect(),
+        status: Err(Some(2)),
+    };
+
+    assert_eq!(
+        format!("{:?}", files),
+        "Files { chunks: [b\"\\x00\\x01\", b\"\"], owner: -, modes: {\"a\": 0x1ed}, status: Err(Some(0x2)) }"
+    );
+}
+
+#[cfg(not(feature = "minimal"))]
+#[test]
+fn test_configured_formatters() {
+    #[derive(crate::Debug)]
+    struct Frame {
+        #[debug(with(HexBuf, max = 2))]
+        header: [u8; 4],
+        #[debug(with(Truncate, max = 2))]
+        samples: [u16; 3],
+        #[debug(with = each(HexBuf { ascii: true, ..Default::default() }))]
+        lines: [&'static [u8]; 1],
+    }
+
+    let frame = Frame {
+        header: [1, 2, 3, 4],
+        samples: [1, 2, 3],
+        lines: [b"ok\n"],
+    };
+
+    assert_eq!(
+        format!("{:?}", frame),
+        "Frame { header: b\"\\x01\\x02\"… (4 bytes), samples: [1, 2, ..], lines: [b\"ok\\x0a\"] }"
+    );
+}
+
+#[cfg(not(feature = "minimal"))]
+#[test]
+fn test_compact_code_output() {
+    macro_rules! protocol {
+        ($name:ident $(, $attr:meta)?) => {
+            #[derive(crate::Debug)]
+            $(#[debug($attr)])?
+            enum $name {
+                Ping,
+                Data(#[debug(with = hexbuf)] std::vec::Vec<u8>, u8),
+                Move {
+                    x: i32,
+                    #[debug(skip_if = Option::is_none)]
+                    y: Option<i32>,
+                    #[debug(format = "{} ms")]
+                    elapsed: u64,
+                },
+                Pong(),
+                Quit {},
+            }
+        };
+    }
+
+    protocol!(Plain);
+    protocol!(Compact, compact_code);
+
+    let values = [
+        (Plain::Ping, Compact::Ping),
+        (
+            Plain::Data(std::vec![1, 2], 3),
+            Compact::Data(std::vec![1, 2], 3),
+        ),
+        (
+            Plain::Move {
+                x: 1,
+                y: None,
+                elapsed: 5,
+            },
+            Compact::Move {
+                x: 1,
+                y: None,
+                elapsed: 5,
+            },
+        ),
+        (
+            Plain::Move {
+                x: 1,
+                y: Some(2),
+                elapsed: 5,
+            },
+            Compact::Move {
+                x: 1,
+                y: Some(2),
+                elapsed: 5,
+            },
+        ),
+        (Plain::Pong(), Compact::Pong()),
+        (Plain::Quit {}, Compact::Quit {}),
+    ];
+
+    for (plain, compact) in &values {
+        assert_eq!(format!("{:?}", compact), format!("{:?}", plain));
+        assert_eq!(format!("{:#?}", compact), format!("{:#?}", plain));
+    }
+}
+
+#[cfg(not(feature = "minimal"))]
+#[test]
+fn test_via_with_format() {
+    use core::time::Duration;
+
+    #[derive(crate::Debug)]
+    struct Request {
+        #[debug(via = Duration::as_millis, with = hex, format = "{} ms")]
+        elapsed: Duration,
+        #[debug(via = Self::body_len, format = "{} bytes")]
+        body: &'static str,
+    }
+
+    impl Request {
+        fn body_len(body: &&str) -> usize {
+            body.len()
+        }
+    }
+
+    let request = Request {
+        elapsed: Duration::from_millis(255),
+        body: "hello",
+    };
+
+    assert_eq!(
+        format!("{:?}", request),
+        "Request { elapsed: 0xff ms, body: 5 bytes }"
+    );
+}
+
+#[cfg(not(feature = "minimal"))]
+#[test]
+fn test_sorted() {
+    use std::collections::{HashMap, HashSet};
+
+    #[derive(crate::Debug)]
+    #[debug(sorted)]
+    struct Index {
+        names: HashMap<u32, &'static str>,
+        #[debug(with = Truncate { max: 3 })]
+        ids: std::vec::Vec<u32>,
+        large: HashSet<u32>,
+    }
+
+    let index = Index {
+        names: [(3, "c"), (1, "a"), (2, "b")].into_iter().collect(),
+        ids: (0..5).rev().collect(),
+        large: (0..40).rev().collect(),
+    };
+
+    let large = (0..40u32)
+        .map(|i| format!("{}", i))
+        .collect::<std::vec::Vec<_>>();
+
+    assert_eq!(
+        format!("{:?}", index),
+        format!(
+            "Index {{ names: {{1: \"a\", 2: \"b\", 3: \"c\"}}, ids: [4, 3, 2, ..], large: {{{}}} }}",
+            large.join(", ")
+        )
+    );
+}
+
+#[cfg(all(feature = "alloc", not(feature = "minimal")))]
+#[test]
+fn test_sorted_by_debug() {
+    #[derive(PartialEq, Eq, Hash, core::fmt::Debug)]
+    enum Color {
+        Red,
+        Green,
+    }
+
+    #[derive(crate::Debug)]
+    struct Palette {
+        #[debug(sorted(by_debug))]
+        colors: std::collections::HashSet<Color>,
+    }
+
+    let palette = Palette {
+        colors: [Color::Red, Color::Green].into_iter().collect(),
+    };
+
+    assert_eq!(format!("{:?}", palette), "Palette { colors: {Green, Red} }");
+}
+
+#[cfg(all(feature = "std", not(feature = "minimal")))]
+#[test]
+fn test_max_depth() {
+    use std::boxed::Box;
+
+    #[derive(crate::Debug)]
+    #[debug(max_depth = 2)]
+    struct Expr {
+        op: &'static str,
+        args: std::vec::Vec<Arg>,
+    }
+
+    #[derive(crate::Debug)]
+    #[debug(max_depth)]
+    enum Arg {
+        Lit(u32),
+        Expr(Box<Expr>),
+    }
+
+    let expr = Expr {
+        op: "+",
+        args: std::vec![
+            Arg::Lit(1),
+            Arg::Expr(Box::new(Expr {
+                op: "-",
+                args: std::vec![Arg::Lit(2)],
+            })),
+        ],
+    };
+
+    assert_eq!(
+        format!("{:?}", expr),
+        "Expr { op: \"+\", args: [Lit(1), Expr({..})] }"
+    );
+    assert_eq!(
+        format!("{:?}", Limited::new(&expr).depth(1)),
+        "Expr { op: \"+\", args: [{..}, {..}] }"
+    );
+    assert_eq!(
+        format!("{:?}", Limited::new(&expr).depth(5)),
+        format!("{:?}", expr)
+    );
+}
+
+#[cfg(all(feature = "std", not(feature = "minimal")))]
+#[test]
+fn test_shared() {
+    use core::cell::RefCell;
+    use std::rc::Rc;
+    use std::sync::Arc;
+
+    #[derive(crate::Debug)]
+    struct Node {
+        name: &'static str,
+        #[debug(with = some(shared))]
+        next: Option<Rc<RefCell<Node>>>,
+    }
+
+    #[derive(crate::Debug)]
+    struct Pair {
+        #[debug(shared)]
+        left: Arc<u8>,
+        #[debug(shared)]
+        right: Arc<u8>,
+    }
+
+    let a = Rc::new(RefCell::new(Node {
+        name: "a",
+        next: None,
+    }));
+    let b = Rc::new(RefCell::new(Node {
+        name: "b",
+        next: Some(a.clone()),
+    }));
+    a.borrow_mut().next = Some(b.clone());
+
+    let expected = "#1 RefCell { value: Node { name: \"a\", next: Some(#2 RefCell { value: Node { name: \"b\", next: Some(<cycle #1>) } }) } }";
+
+    assert_eq!(format!("{:?}", DebugWith::new(&a, shared)), expected);
+    // The labels start over with every format call
+    assert_eq!(format!("{:?}", DebugWith::new(&a, shared)), expected);
+
+    b.borrow_mut().next = None;
+
+    let value = Arc::new(7);
+    let pair = Pair {
+        left: value.clone(),
+        right: value,
+    };
+
+    assert_eq!(
+        format!("{:?}", pair),
+        "Pair { left: #1 7, right: <seen #1> }"
+    );
+
+    #[derive(crate::Debug)]
+    struct OptPair {
+        #[debug(with = some(shared))]
+        a: Option<Rc<u8>>,
+        #[debug(with = some(shared))]
+        b: Option<Rc<u8>>,
+    }
+
+    let value = Rc::new(5);
+    let pair = OptPair {
+        a: Some(value.clone()),
+        b: Some(value),
+    };
+
+    assert_eq!(
+        format!("{:?}", pair),
+        "OptPair { a: Some(#1 5), b: Some(<seen #1>) }"
+    );
+}
+
+#[cfg(not(feature = "minimal"))]
+#[test]
+fn test_capped() {
+    #[derive(crate::Debug)]
+    #[debug(max_bytes = 30)]
+    struct Message {
+        text: &'static str,
+    }
+
+    let text = "héllo wörld, héllo wörld";
+    let exact = format!("{:?}", text);
+
+    assert_eq!(format!("{:?}", Capped::new(&text, exact.len())), exact);
+    // Does not split the `ö`
+    assert_eq!(
+        format!("{:?}", Capped::new(&text, 24)),
+        "\"héllo w…[truncated]"
+    );
+    assert_eq!(format!("{:?}", Capped::new(&text, 5)), "\"h…");
+    assert_eq!(format!("{:?}", Capped::new(&text, 2)), "..");
+    assert_eq!(format!("{:?}", Capped::new(&"ab", 4)), "\"ab\"");
+    assert_eq!(
+        format!("{:#?}", Capped::new(&[1, 2], 100)),
+        "[\n    1,\n    2,\n]"
+    );
+    assert_eq!(
+        format!(
+            "{:*>4?} {:05.1?}",
+            Capped::new(&[1], 100),
+            Capped::new(&2.0, 100)
+        ),
+        "[***1] 002.0"
+    );
+    assert_eq!(format!("{:%^3?}", Capped::new(&1, 100)), " 1 ");
+
+    let message = Message { text: "short" };
+    assert_eq!(format!("{:?}", message), "Message { text: \"short\" }");
+
+    let message = Message {
+        text: "a bit too long",
+    };
+    let output = format!("{:?}", message);
+    assert_eq!(output, "Message { text: …[truncated]");
+    assert!(output.len() <= 30);
+}
+
+#[cfg(not(feature = "minimal"))]
+#[test]
+fn test_render_into() {
+    use core::fmt::Write;
+
+    #[derive(crate::Debug)]
+    struct Reading {
+        #[debug(format = "{} °C")]
+        temperature: i8,
+    }
+
+    let reading = Reading { temperature: 21 };
+
+    let mut buf = [0; 64];
+    let rendered = render_into(&reading, &mut buf);
+    assert_eq!(rendered.text, "Reading { temperature: 21 °C }");
+    assert!(!rendered.is_truncated());
+
+    // Drops the whole `°`
+    let mut buf = [0; 27];
+    let rendered = render_into(&reading, &mut buf);
+    assert_eq!(&*rendered, "Reading { temperature: 21 ");
+    assert_eq!(rendered.dropped, 5);
+
+    let mut buf = [0; 64];
+    let rendered = render_into_pretty(&reading, &mut buf);
+    assert_eq!(rendered.text, "Reading {\n    temperature: 21 °C,\n}");
+
+    let mut s = ArrayString::<8>::new();
+    write!(s, "{:?}", reading).unwrap();
+    assert_eq!(s.as_str(), "Reading ");
+    assert_eq!(s.dropped(), 23);
+
+    s.clear();
+    write!(s, "{:?}", "ok").unwrap();
+    assert_eq!(format!("{} {:?}", s, s), "\"ok\" \"\\\"ok\\\"\"");
+}
+
+#[cfg(not(feature = "minimal"))]
+#[test]
+fn test_profiles() {
+    struct Log;
+    struct Terse;
+
+    #[derive(crate::Debug)]
+    struct Request {
+        #[debug(profile(terse), format = "{}")]
+        path: &'static str,
+        #[debug(profile(log, terse), skip)]
+        token: &'static str,
+        #[debug(with = hex)]
+        #[debug(profile(terse), skip)]
+        flags: u8,
+        #[debug(profile(log), with = in_profile::<Log>)]
+        user: User,
+    }
+
+    #[derive(crate::Debug)]
+    enum User {
+        Named {
+            name: &'static str,
+            #[debug(profile(log), skip)]
+            email: &'static str,
+        },
+    }
+
+    let request = Request {
+        path: "/login",
+        token: "secret",
+        flags: 16,
+        user: User::Named {
+            name: "ferris",
+            email: "ferris@example.com",
+        },
+    };
+
+    assert_eq!(
+        format!("{:?}", request),
+        "Request { path: \"/login\", token: \"secret\", flags: 0x10, user: Named { name: \"ferris\", email: \"ferris@example.com\" } }"
+    );
+    assert_eq!(
+        format!("{:?}", Profile::<Log>::of(&request)),
+        "Request { path: \"/login\", flags: 0x10, user: Named { name: \"ferris\" } }"
+    );
+    assert_eq!(
+        format!("{:?}", Profile::<Terse>::of(&request)),
+        "Request { path: /login, user: Named { name: \"ferris\", email: \"ferris@example.com\" } }"
+    );
+
+    #[derive(crate::Debug)]
+    struct Wrapper<T> {
+        value: T,
+        #[debug(profile(log), skip)]
+        id: u32,
+    }
+
+    let wrapper = Wrapper { value: 1u8, id: 7 };
+
+    assert_eq!(format!("{:?}", wrapper), "Wrapper { value: 1, id: 7 }");
+    assert_eq!(
+        format!("{:?}", Profile::<Log>::of(&wrapper)),
+        "Wrapper { value: 1 }"
+    );
+}
+
+#[cfg(all(feature = "std", not(feature = "minimal")))]
+#[test]
+fn test_with_filter() {
+    #[derive(crate::Debug)]
+    #[debug(filter)]
+    struct Tenant {
+        name: &'static str,
+        contact: Contact,
+    }
+
+    #[derive(crate::Debug)]
+    #[debug(compact_code, filter)]
+    enum Contact {
+        Email {
+            address: &'static str,
+            verified: bool,
+        },
+    }
+
+    let tenant = Tenant {
+        name: "acme",
+        contact: Contact::Email {
+            address: "ops@acme.test",
+            verified: true,
+        },
+    };
+
+    let hide_address =
+        |type_name: &str, field_name: &str| (type_name, field_name) != ("Contact", "address");
+    let hide_name = |_: &str, field_name: &str| field_name != "name";
+
+    assert_eq!(
+        with_filter(hide_address, || format!("{:?}", tenant)),
+        "Tenant { name: \"acme\", contact: Email { verified: true } }"
+    );
+    assert_eq!(
+        with_filter(hide_address, || with_filter(hide_name, || format!(
+            "{:?}",
+            tenant
+        ))),
+        "Tenant { contact: Email { verified: true } }"
+    );
+    assert_eq!(
+        format!("{:?}", tenant),
+        "Tenant { name: \"acme\", contact: Email { address: \"ops@acme.test\", verified: true } }"
+    );
+
+    // Tuple fields have no name and are always shown
+    #[derive(crate::Debug)]
+    #[debug(filter)]
+    struct Pair(u8, u8);
+
+    assert_eq!(
+        with_filter(|_, _| false, || format!("{:?}", Pair(1, 2))),
+        "Pair(1, 2)"
+    );
+
+    // Types without the `filter` attribute show all fields
+    #[derive(crate::Debug)]
+    struct Unfiltered {
+        name: &'static str,
+    }
+
+    assert_eq!(
+        with_filter(
+            |_, _| false,
+            || format!("{:?}", Unfiltered { name: "acme" })
+        ),
+        "Unfiltered { name: \"acme\" }"
+    );
+
+    // Values formatted by a filter aren't filtered
+    let formats_tenant = |_: &str, field_name: &str| {
+        format!("{:?}", tenant).contains(field_name) && field_name != "verified"
+    };
+
+    assert_eq!(
+        with_filter(formats_tenant, || format!("{:?}", tenant)),
+        "Tenant { name: \"acme\", contact: Email { address: \"ops@acme.test\" } }"
+    );
+}
+
+#[cfg(not(feature = "minimal"))]
+#[test]
+fn test_levels() {
+    #[derive(crate::Debug)]
+    struct Job {
+        id: u32,
+        #[debug(level = 1)]
+        state: &'static str,
+        #[debug(level = 2, skip_if = Option::is_none)]
+        error: Option<&'static str>,
+    }
+
+    let job = Job {
+        id: 1,
+        state: "failed",
+        error: Some("timeout"),
+    };
+
+    assert_eq!(format!("{:?}", job), "Job { id: 1 }");
+    assert_eq!(
+        format!("{:#?}", job),
+        "Job {\n    id: 1,\n    state: \"failed\",\n}"
+    );
+
+    #[cfg(feature = "std")]
+    {
+        assert_eq!(
+            format!("{:?}", Verbose::new(&job, 2)),
+            "Job { id: 1, state: \"failed\", error: Some(\"timeout\") }"
+        );
+        assert_eq!(
+            format!("{:#?}", Verbose::new(&job, 0)),
+            "Job {\n    id: 1,\n}"
+        );
+    }
+}
+
+#[cfg(not(feature = "minimal"))]
+#[test]
+fn test_redact_and_sensitive() {
+    #[derive(crate::Debug)]
+    struct Credentials {
+        user: &'static str,
+        #[debug(redact)]
+        password: &'static str,
+        #[debug(with = hexbuf, redact)]
+        key: [u8; 2],
+        #[debug(skip(sensitive))]
+        recovery_code: u32,
+    }
+
+    let credentials = Credentials {
+        user: "ferris",
+        password: "hunter2",
+        key: [0xab, 0xcd],
+        recovery_code: 1234,
+    };
+
+    let expected = match cfg!(custom_debug_reveal) {
+        true => "Credentials { user: \"ferris\", password: \"hunter2\", key: b\"\\xab\\xcd\", recovery_code: 1234 }",
+        false => "Credentials { user: \"ferris\", password: <redacted>, key: <redacted> }",
+    };
+
+    assert_eq!(format!("{:?}", credentials), expected);
+}
+
+#[cfg(feature = "defmt")]
+#[test]
+fn test_defmt() {
+    #[derive(crate::Debug)]
+    #[debug(defmt)]
+    struct Reading<T> {
+        value: T,
+        #[debug(with = hexbuf)]
+        raw: [u8; 2],
+        #[debug(skip_if = Option::is_none)]
+        error: Option<u8>,
+    }
+
+    fn assert_format<T: defmt::Format>(_: &T) {}
+
+    let reading = Reading {
+        value: 7u16,
+        raw: [0xab, 0xcd],
+        error: None,
+    };
+
+    assert_format(&reading);
+    #[cfg(not(feature = "minimal"))]
+    assert_eq!(
+        format!("{:?}", reading),
+        "Reading { value: 7, raw: b\"\\xab\\xcd\" }"
+    );
+}
+
+#[cfg(all(feature = "valuable", feature = "alloc"))]
+#[test]
+fn test_valuable() {
+    use core::fmt::Write;
+    use valuable::{Enumerable, NamedValues, Valuable, Value, Visit};
+
+    #[derive(crate::Debug)]
+    #[debug(valuable)]
+    struct Login {
+        user: &'static str,
+        #[debug(redact)]
+        password: &'static str,
+        #[debug(with = hexbuf)]
+        token: [u8; 2],
+        #[debug(skip_if = Option::is_none)]
+        note: Option<u32>,
+    }
+
+    #[derive(crate::Debug)]
+    #[debug(valuable)]
+    enum Event {
+        Login(Login),
+        Logout,
+    }
+
+    struct Fields(alloc::string::String);
+
+    impl Visit for Fields {
+        fn visit_value(&mut self, value: Value<'_>) {
+            if let Value::Structable(value) = value {
+                value.visit(self);
+            }
+        }
+
+        fn visit_named_fields(&mut self, named_values: &NamedValues<'_>) {
+            for (field, value) in named_values {
+                write!(self.0, " {}={:?}", field.name(), value).unwrap();
+            }
+        }
+
+        fn visit_unnamed_fields(&mut self, values: &[Value<'_>]) {
+            for value in values {
+                self.visit_value(*value);
+            }
+        }
+    }
+
+    let event = Event::Login(Login {
+        user: "ferris",
+        password: "hunter2",
+        token: [0xab, 0xcd],
+        note: None,
+    });
+    let mut fields = Fields(event.variant().name().into());
+
+    event.visit(&mut fields);
+
+    let password = match cfg!(custom_debug_reveal) {
+        true => "\"\\\"hunter2\\\"\"",
+        false => "\"<redacted>\"",
+    };
+
+    assert_eq!(
+        fields.0,
+        format!(
+            "Login user=\"ferris\" password={} token=\"b\\\"\\\\xab\\\\xcd\\\"\" note=()",
+            password
+        )
+    );
+    assert_eq!(Event::Logout.variant().name(), "Logout");
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serialize() {
+    use crate::DebugView;
+
+    #[derive(crate::Debug)]
+    #[debug(serialize)]
+    enum Event {
+        Login {
+            user: &'static str,
+            #[debug(redact)]
+            password: &'static str,
+            #[debug(with = hexbuf)]
+            token: [u8; 2],
+            #[debug(skip_if = Option::is_none)]
+            note: Option<u32>,
+        },
+        Retry(u8, #[debug(format = "{} ms")] u32),
+        Logout,
+    }
+
+    let login = Event::Login {
+        user: "ferris",
+        password: "hunter2",
+        token: [0xab, 0xcd],
+        note: None,
+    };
+    let password = match cfg!(custom_debug_reveal) {
+        true => r#""\"hunter2\"""#,
+        false => r#""<redacted>""#,
+    };
+
+    assert_eq!(
+        serde_json::to_string(&DebugView(&login)).unwrap(),
+        format!(
+            r#"{{"Login":{{"user":"ferris","password":{},"token":"b\"\\xab\\xcd\""}}}}"#,
+            password
+        )
+    );
+    assert_eq!(
+        serde_json::to_string(&DebugView(&Event::Retry(2, 250))).unwrap(),
+        r#"{"Retry":[2,"250 ms"]}"#
+    );
+    assert_eq!(
+        serde_json::to_string(&DebugView(&Event::Logout)).unwrap(),
+        r#""Logout""#
+    );
+}
+
+#[test]
+fn test_visit() {
+    use crate::{ArrayString, Visit, Visitor};
+    use core::fmt::Write;
+
+    #[derive(crate::Debug)]
+    #[debug(visit)]
+    enum Job {
+        Queued {
+            id: u32,
+            #[debug(level = 1)]
+            retries: u32,
+            #[debug(skip_if = Option::is_none)]
+            owner: Option<&'static str>,
+        },
+        Failed(#[debug(with = hexbuf)] [u8; 2]),
+    }
+
+    struct Logfmt(ArrayString<64>);
+
+    impl Visitor for Logfmt {
+        fn variant(&mut self, _: &str, name: &str) {
+            let _ = write!(self.0, "{}", name);
+        }
+
+        fn field(&mut self, name: &str, value: &dyn fmt::Debug) {
+            let _ = write!(self.0, " {}={:?}", name, value);
+        }
+
+        fn end(&mut self) {
+            let _ = write!(self.0, ";");
+        }
+    }
+
+    let mut logfmt = Logfmt(ArrayString::new());
+
+    Job::Queued {
+        id: 7,
+        retries: 2,
+        owner: None,
+    }
+    .visit(&mut logfmt);
+    Job::Failed([0xab, 0xcd]).visit(&mut logfmt);
+
+    assert_eq!(logfmt.0.as_str(), "Queued id=7;Failed 0=b\"\\xab\\xcd\";");
+}
+
+#[cfg(all(
+    feature = "std",
+    feature = "serde",
+    feature = "valuable",
+    not(feature = "minimal")
+))]
+#[test]
+fn test_views_show_the_fields_of_debug() {
+    use crate::{with_filter, with_verbosity, DebugView, Visit, Visitor};
+    use alloc::string::String;
+    use valuable::{NamedValues, Valuable, Value};
+
+    #[derive(crate::Debug)]
+    #[debug(filter, visit, serialize, valuable)]
+    struct Job {
+        id: u32,
+        owner: &'static str,
+        #[debug(level = 1)]
+        retries: u32,
+    }
+
+    struct Names(String);
+
+    impl Visitor for Names {
+        fn field(&mut self, name: &str, _: &dyn fmt::Debug) {
+            self.0 += name;
+        }
+    }
+
+    struct ShownNames(String);
+
+    impl valuable::Visit for ShownNames {
+        fn visit_value(&mut self, _: Value<'_>) {}
+
+        fn visit_named_fields(&mut self, named_values: &NamedValues<'_>) {
+            for (field, value) in named_values {
+                if !matches!(value, Value::Unit) {
+                    self.0 += field.name();
+                }
+            }
+        }
+    }
+
+    let job = Job {
+        id: 1,
+        owner: "ferris",
+        retries: 3,
+    };
+    let views = || {
+        let mut names = Names(String::new());
+        let mut shown_names = ShownNames(String::new());
+
+        Visit::visit(&job, &mut names);
+        Valuable::visit(&job, &mut shown_names);
+
+        (
+            format!("{:?}", job),
+            serde_json::to_string(&DebugView(&job)).unwrap(),
+            names.0,
+            shown_names.0,
+        )
+    };
+
+    assert_eq!(
+        with_filter(|_, field| field != "owner", views),
+        (
+            "Job { id: 1 }".into(),
+            r#"{"id":1}"#.into(),
+            "id".into(),
+            "id".into()
+        )
+    );
+    assert_eq!(
+        with_verbosity(1, views),
+        (
+            "Job { id: 1, owner: \"ferris\", retries: 3 }".into(),
+            r#"{"id":1,"owner":"ferris","retries":3}"#.into(),
+            "idownerretries".into(),
+            "idownerretries".into()
+        )
+    );
+}
+
+#[cfg(feature = "minimal")]
+#[test]
+fn test_minimal() {
+    #[derive(crate::Debug)]
+    enum Event {
+        Started,
+        Progress(#[debug(with = hex)] u32),
+        Finished {
+            #[debug(skip_if = Option::is_none)]
+            error: Option<u8>,
+        },
+    }
+
+    #[derive(crate::Debug)]
+    struct Job {
+        #[debug(format = "#{}")]
+        id: u32,
+    }
+
+    assert_eq!(format!("{:?}", Event::Started), "Event::Started");
+    assert_eq!(format!("{:#?}", Event::Progress(7)), "Event::Progress");
+    assert_eq!(
+        format!("{:?}", Event::Finished { error: None }),
+        "Event::Finished"
+    );
+    assert_eq!(format!("{:?}", Job { id: 1 }), "Job");
+}
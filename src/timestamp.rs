@@ -0,0 +1,146 @@
+use core::fmt;
+
+/// A UTC calendar date and time, converted from a Unix timestamp
+/// without any external date/time crate.
+///
+/// The [Display](fmt::Display) impl renders RFC 3339 (`2026-10-18T12:00:00Z`).
+/// A precision (`{:.3}`) selects the number of fractional second digits;
+/// without one, the fraction is only printed if it is non-zero.
+#[derive(Clone, Copy, PartialEq, Eq, core::fmt::Debug)]
+pub struct UtcDateTime {
+    pub year: i64,
+    pub month: u8,
+    pub day: u8,
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+    pub nanosecond: u32,
+}
+
+impl UtcDateTime {
+    /// Converts seconds and a sub-second nanosecond part since the Unix epoch.
+    pub const fn from_unix(secs: u64, nanosecond: u32) -> Self {
+        let days = (secs / 86400) as i64;
+        let secs_of_day = secs % 86400;
+        let (year, month, day) = civil_from_days(days);
+
+        Self {
+            year,
+            month,
+            day,
+            hour: (secs_of_day / 3600) as u8,
+            minute: (secs_of_day / 60 % 60) as u8,
+            second: (secs_of_day % 60) as u8,
+            nanosecond,
+        }
+    }
+
+    /// Converts a timestamp given in `unit`s since the Unix epoch.
+    pub const fn from_unix_in(value: u64, unit: TimeUnit) -> Self {
+        let per_sec = unit.per_second();
+        let nanosecond = (value % per_sec) * (1_000_000_000 / per_sec);
+
+        Self::from_unix(value / per_sec, nanosecond as u32)
+    }
+}
+
+impl fmt::Display for UtcDateTime {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+            self.year, self.month, self.day, self.hour, self.minute, self.second
+        )?;
+
+        let digits = match f.precision() {
+            Some(precision) => precision.min(9),
+            None if self.nanosecond == 0 => 0,
+            None => 9,
+        };
+
+        if digits > 0 {
+            let fraction = self.nanosecond / 10u32.pow(9 - digits as u32);
+            write!(f, ".{:0width$}", fraction, width = digits)?;
+        }
+
+        write!(f, "Z")
+    }
+}
+
+/// Resolution of a Unix timestamp.
+#[derive(Clone, Copy, PartialEq, Eq, core::fmt::Debug)]
+pub enum TimeUnit {
+    Seconds,
+    Millis,
+    Micros,
+    Nanos,
+}
+
+impl TimeUnit {
+    const fn per_second(self) -> u64 {
+        match self {
+            TimeUnit::Seconds => 1,
+            TimeUnit::Millis => 1_000,
+            TimeUnit::Micros => 1_000_000,
+            TimeUnit::Nanos => 1_000_000_000,
+        }
+    }
+
+    const fn fraction_digits(self) -> usize {
+        match self {
+            TimeUnit::Seconds => 0,
+            TimeUnit::Millis => 3,
+            TimeUnit::Micros => 6,
+            TimeUnit::Nanos => 9,
+        }
+    }
+}
+
+/// Formats a Unix timestamp in seconds as its raw value followed by the RFC 3339 date.
+pub fn timestamp_secs<T: Copy + Into<u64>>(v: &T, f: &mut fmt::Formatter) -> fmt::Result {
+    timestamp((*v).into(), TimeUnit::Seconds, f)
+}
+
+/// Formats a Unix timestamp in milliseconds as its raw value followed by the RFC 3339 date.
+pub fn timestamp_millis<T: Copy + Into<u64>>(v: &T, f: &mut fmt::Formatter) -> fmt::Result {
+    timestamp((*v).into(), TimeUnit::Millis, f)
+}
+
+/// Formats a Unix timestamp in microseconds as its raw value followed by the RFC 3339 date.
+pub fn timestamp_micros<T: Copy + Into<u64>>(v: &T, f: &mut fmt::Formatter) -> fmt::Result {
+    timestamp((*v).into(), TimeUnit::Micros, f)
+}
+
+/// Formats a Unix timestamp in nanoseconds as its raw value followed by the RFC 3339 date.
+pub fn timestamp_nanos<T: Copy + Into<u64>>(v: &T, f: &mut fmt::Formatter) -> fmt::Result {
+    timestamp((*v).into(), TimeUnit::Nanos, f)
+}
+
+fn timestamp(value: u64, unit: TimeUnit, f: &mut fmt::Formatter) -> fmt::Result {
+    let date_time = UtcDateTime::from_unix_in(value, unit);
+
+    write!(
+        f,
+        "{} ({:.digits$})",
+        value,
+        date_time,
+        digits = unit.fraction_digits()
+    )
+}
+
+/// Converts days since 1970-01-01 to a (year, month, day) triple in the proleptic Gregorian calendar.
+///
+/// See <https://howardhinnant.github.io/date_algorithms.html#civil_from_days>.
+const fn civil_from_days(days: i64) -> (i64, u8, u8) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + (month <= 2) as i64;
+
+    (year, month as u8, day as u8)
+}
@@ -0,0 +1,90 @@
+use core::cell::Cell;
+use core::fmt;
+
+use crate::depth::scoped;
+
+std::thread_local! {
+    /// Verbosity set by the innermost [Verbose] on this thread.
+    static VERBOSITY: Cell<Option<usize>> = const { Cell::new(None) };
+}
+
+/// Formats a value with the given verbosity, which decides
+/// which fields with a `level` attribute derived impls show.
+///
+/// Fields are shown if their level is at most the verbosity.
+/// This takes precedence over the alternate flag (`{:#?}`), which otherwise selects level 1.
+///
+/// ```
+/// use custom_debug::{Debug, Verbose};
+///
+/// #[derive(Debug)]
+/// struct Job {
+///     id: u32,
+///     #[debug(level = 2)]
+///     retries: u32,
+/// }
+///
+/// let job = Job { id: 1, retries: 3 };
+///
+/// # #[cfg(not(feature = "minimal"))]
+/// assert_eq!(format!("{:?}", job), "Job { id: 1 }");
+/// # #[cfg(not(feature = "minimal"))]
+/// assert_eq!(format!("{:?}", Verbose::new(&job, 2)), "Job { id: 1, retries: 3 }");
+/// ```
+pub struct Verbose<'a, T: ?Sized> {
+    value: &'a T,
+    level: usize,
+}
+
+impl<'a, T: ?Sized> Verbose<'a, T> {
+    pub fn new(value: &'a T, level: usize) -> Self {
+        Self { value, level }
+    }
+}
+
+impl<T: fmt::Debug + ?Sized> fmt::Debug for Verbose<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        with_verbosity(self.level, || self.value.fmt(f))
+    }
+}
+
+/// Runs `f` with the given verbosity, like [Verbose] does while formatting.
+///
+/// This selects the fields with a `level` attribute that impls other than `Debug` show,
+/// such as the ones derived with `visit` or `serialize`.
+///
+/// ```
+/// use custom_debug::{with_verbosity, ArrayString, Debug, Visit, Visitor};
+/// use core::fmt::{self, Write};
+///
+/// #[derive(Debug)]
+/// #[debug(visit)]
+/// struct Job {
+///     id: u32,
+///     #[debug(level = 2)]
+///     retries: u32,
+/// }
+///
+/// struct Names(ArrayString<32>);
+///
+/// impl Visitor for Names {
+///     fn field(&mut self, name: &str, _: &dyn fmt::Debug) {
+///         let _ = write!(self.0, "{} ", name);
+///     }
+/// }
+///
+/// let job = Job { id: 1, retries: 3 };
+/// let mut names = Names(ArrayString::new());
+///
+/// with_verbosity(2, || job.visit(&mut names));
+///
+/// # #[cfg(not(feature = "minimal"))]
+/// assert_eq!(names.0.as_str(), "id retries ");
+/// ```
+pub fn with_verbosity<R>(level: usize, f: impl FnOnce() -> R) -> R {
+    scoped(&VERBOSITY, Some(level), f)
+}
+
+pub(crate) fn verbosity() -> Option<usize> {
+    VERBOSITY.with(Cell::get)
+}
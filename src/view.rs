@@ -0,0 +1,48 @@
+use serde::{Serialize, Serializer};
+
+/// Serializes the fields a type shows with [Debug](core::fmt::Debug).
+///
+/// Derived by `#[debug(serialize)]` and used through [DebugView].
+pub trait SerializeDebug {
+    fn serialize_debug<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>;
+}
+
+/// Serializes a value like it is shown by its [Debug](core::fmt::Debug) impl,
+/// independent of any `Serialize` impl of its own.
+///
+/// Skipped fields are left out, and fields using a formatter are serialized as strings
+/// of their output, so redacted fields only ever show up as `<redacted>`.
+///
+/// ```
+/// use custom_debug::{Debug, DebugView};
+///
+/// #[derive(Debug)]
+/// #[debug(serialize)]
+/// struct Request {
+///     path: &'static str,
+///     #[debug(format = "{} ms")]
+///     timeout: u32,
+///     #[debug(skip)]
+///     attempts: u32,
+/// }
+///
+/// let request = Request {
+///     path: "/status",
+///     timeout: 250,
+///     attempts: 3,
+/// };
+///
+/// assert_eq!(
+///     serde_json::to_string(&DebugView(&request)).unwrap(),
+///     r#"{"path":"/status","timeout":"250 ms"}"#,
+/// );
+/// # let _ = request.attempts;
+/// ```
+#[derive(Clone, Copy)]
+pub struct DebugView<T>(pub T);
+
+impl<T: SerializeDebug + ?Sized> Serialize for DebugView<&T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize_debug(serializer)
+    }
+}
@@ -0,0 +1,68 @@
+use core::fmt;
+
+/// Passes the fields a type shows with [Debug](fmt::Debug) to a [Visitor].
+///
+/// Derived by `#[debug(visit)]`. Fields are passed in the order `Debug` shows them,
+/// as the values `Debug` would format, so skipped fields are left out
+/// and redacted fields only ever show up as `<redacted>`.
+///
+/// ```
+/// use core::fmt::{self, Write};
+/// use custom_debug::{ArrayString, Debug, Visit, Visitor};
+///
+/// #[derive(Debug)]
+/// #[debug(visit)]
+/// struct Request {
+///     path: &'static str,
+///     #[debug(format = "{} ms")]
+///     timeout: u32,
+///     #[debug(skip)]
+///     attempts: u32,
+/// }
+///
+/// /// Writes fields as logfmt
+/// struct Logfmt(ArrayString<64>);
+///
+/// impl Visitor for Logfmt {
+///     fn field(&mut self, name: &str, value: &dyn fmt::Debug) {
+///         let _ = write!(self.0, "{}={:?} ", name, value);
+///     }
+/// }
+///
+/// let request = Request {
+///     path: "/status",
+///     timeout: 250,
+///     attempts: 3,
+/// };
+/// let mut logfmt = Logfmt(ArrayString::new());
+///
+/// request.visit(&mut logfmt);
+///
+/// assert_eq!(logfmt.0.as_str(), "path=\"/status\" timeout=250 ms ");
+/// # let _ = request.attempts;
+/// ```
+pub trait Visit {
+    fn visit(&self, visitor: &mut dyn Visitor);
+}
+
+/// Receives the fields of a value from its [Visit] impl.
+///
+/// A struct is passed as [struct_start](Self::struct_start), its fields and [end](Self::end).
+/// A variant of an enum starts with [variant](Self::variant) instead.
+pub trait Visitor {
+    /// Starts a struct with the name `name`.
+    fn struct_start(&mut self, name: &str) {
+        let _ = name;
+    }
+
+    /// Starts the variant `name` of the enum `type_name`.
+    fn variant(&mut self, type_name: &str, name: &str) {
+        let _ = (type_name, name);
+    }
+
+    /// A field, named by its index (`"0"`) if it has no name.
+    fn field(&mut self, name: &str, value: &dyn fmt::Debug);
+
+    /// Ends the struct or variant.
+    fn end(&mut self) {}
+}